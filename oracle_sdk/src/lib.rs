@@ -1,12 +1,23 @@
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash::hash;
 use anchor_lang::solana_program::instruction::Instruction;
-use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::program::{get_return_data, invoke};
+use anchor_lang::system_program;
+use std::cell::Ref;
+
+pub mod merkle;
 
 pub const APRO_SVM_PROGRAM_ID: &str = "AfeSbLSZ8zMVTVBj4ALAHAbE6VnfM6s9ThcWETKKotkq";
 
 pub fn load_price_feed_from_account_info(price_account_info: &AccountInfo) -> Result<PriceFeed> {
     let data = price_account_info.try_borrow_data()?;
+    require_eq!(
+        data.len(),
+        PriceFeed::LEN,
+        ErrorCode::AccountDidNotDeserialize
+    );
 
     let mut price_feed_data = &data[8..];
     let price_feed = PriceFeed::deserialize(&mut price_feed_data)?;
@@ -14,13 +25,235 @@ pub fn load_price_feed_from_account_info(price_account_info: &AccountInfo) -> Re
     Ok(price_feed)
 }
 
+/// Loads a price feed together with its config, failing if the feed is
+/// retired at `now`. A deprecated feed loads, with a warning logged.
+pub fn load_live_price_feed(
+    price_account_info: &AccountInfo,
+    config_account_info: &AccountInfo,
+    now: i64,
+) -> Result<(PriceFeed, FeedConfig)> {
+    let price_feed = load_price_feed_from_account_info(price_account_info)?;
+    let feed_config = load_feed_config_from_account_info(config_account_info)?;
+    require!(
+        feed_config.feed_id == price_feed.feed_id,
+        SdkError::FeedConfigMismatch
+    );
+
+    match feed_config.effective_status(now) {
+        FeedStatus::Active => {}
+        FeedStatus::Deprecated => match feed_config.seconds_until_sunset(now) {
+            Some(seconds) => msg!("Feed is deprecated and retires in {}s", seconds),
+            None => msg!("Feed is deprecated"),
+        },
+        FeedStatus::Retired => return err!(SdkError::FeedRetired),
+    }
+
+    Ok((price_feed, feed_config))
+}
+
+/// Borrows a price feed account without copying it; see [`PriceFeedRef`].
+pub fn load_price_feed_ref<'a>(price_account_info: &'a AccountInfo) -> Result<PriceFeedRef<'a>> {
+    let data = price_account_info.try_borrow_data()?;
+    require_eq!(
+        data.len(),
+        PriceFeed::LEN,
+        ErrorCode::AccountDidNotDeserialize
+    );
+
+    Ok(PriceFeedRef {
+        data: Ref::map(data, |data| &data[8..]),
+    })
+}
+
+/// Loads a price history account and returns its entries ordered from the
+/// oldest to the most recent one.
+pub fn load_price_history_from_account_info(
+    history_account_info: &AccountInfo,
+) -> Result<PriceHistory> {
+    let data = history_account_info.try_borrow_data()?;
+
+    let mut history_data = &data[8..];
+    let header = PriceHistoryHeader::deserialize(&mut history_data)?;
+
+    let mut ring = Vec::with_capacity(header.capacity as usize);
+    for _ in 0..header.capacity {
+        ring.push(PriceHistoryEntry::deserialize(&mut history_data)?);
+    }
+
+    // Once the ring has wrapped, `head` points at the oldest entry.
+    let start = if header.len == header.capacity {
+        header.head as usize
+    } else {
+        0
+    };
+    let entries = (0..header.len as usize)
+        .map(|i| ring[(start + i) % ring.len()].clone())
+        .collect();
+
+    Ok(PriceHistory {
+        oracle_state: header.oracle_state,
+        feed_id: header.feed_id,
+        entries,
+    })
+}
+
+pub fn load_price_accumulator_from_account_info(
+    accumulator_account_info: &AccountInfo,
+) -> Result<PriceAccumulator> {
+    let data = accumulator_account_info.try_borrow_data()?;
+
+    let mut accumulator_data = &data[8..];
+    let price_accumulator = PriceAccumulator::deserialize(&mut accumulator_data)?;
+
+    Ok(price_accumulator)
+}
+
+/// Time-weighted average of `benchmark_price` over the last `window` seconds
+/// ending at the current cluster time. See [`PriceAccumulator::twap_at`].
+pub fn twap(accumulator: &PriceAccumulator, window: u128) -> Result<Option<u128>> {
+    let now = Clock::get()?.unix_timestamp as u128;
+    Ok(accumulator.twap_at(window, now))
+}
+
+/// Exponential moving average of `benchmark_price` as of the last update.
+/// See [`PriceAccumulator::ema_price`].
+pub fn ema(accumulator: &PriceAccumulator) -> u128 {
+    accumulator.ema_price
+}
+
+pub fn load_feed_config_from_account_info(config_account_info: &AccountInfo) -> Result<FeedConfig> {
+    let data = config_account_info.try_borrow_data()?;
+
+    let mut feed_config_data = &data[8..];
+    let feed_config = FeedConfig::deserialize(&mut feed_config_data)?;
+
+    Ok(feed_config)
+}
+
+pub fn load_feed_registry_from_account_info(
+    registry_account_info: &AccountInfo,
+) -> Result<FeedRegistry> {
+    let data = registry_account_info.try_borrow_data()?;
+
+    let mut registry_data = &data[8..];
+    let feed_registry = FeedRegistry::deserialize(&mut registry_data)?;
+
+    Ok(feed_registry)
+}
+
+pub fn load_feed_registry_page_from_account_info(
+    page_account_info: &AccountInfo,
+) -> Result<FeedRegistryPage> {
+    let data = page_account_info.try_borrow_data()?;
+
+    let mut page_data = &data[8..];
+    let feed_registry_page = FeedRegistryPage::deserialize(&mut page_data)?;
+
+    Ok(feed_registry_page)
+}
+
+/// Lists the feeds recorded in `pages`, in creation order. Pass every page
+/// of the registry, ordered by index; see [`feed_registry_page_addresses`].
+pub fn list_registered_feeds(pages: &[AccountInfo]) -> Result<Vec<RegisteredFeed>> {
+    let mut feeds = Vec::new();
+    for page in pages {
+        feeds.extend(load_feed_registry_page_from_account_info(page)?.feeds);
+    }
+
+    Ok(feeds)
+}
+
+pub fn load_data_feed_from_account_info(data_account_info: &AccountInfo) -> Result<DataFeed> {
+    let data = data_account_info.try_borrow_data()?;
+
+    let mut data_feed_data = &data[8..];
+    let data_feed = DataFeed::deserialize(&mut data_feed_data)?;
+
+    Ok(data_feed)
+}
+
+pub fn load_signer_stats_from_account_info(
+    stats_account_info: &AccountInfo,
+) -> Result<SignerStats> {
+    let data = stats_account_info.try_borrow_data()?;
+
+    let mut signer_stats_data = &data[8..];
+    let signer_stats = SignerStats::deserialize(&mut signer_stats_data)?;
+
+    Ok(signer_stats)
+}
+
+pub fn load_aggregate_feed_from_account_info(
+    aggregate_account_info: &AccountInfo,
+) -> Result<AggregateFeed> {
+    let data = aggregate_account_info.try_borrow_data()?;
+
+    let mut aggregate_feed_data = &data[8..];
+    let aggregate_feed = AggregateFeed::deserialize(&mut aggregate_feed_data)?;
+
+    Ok(aggregate_feed)
+}
+
+pub fn load_derived_feed_from_account_info(
+    derived_account_info: &AccountInfo,
+) -> Result<DerivedFeed> {
+    let data = derived_account_info.try_borrow_data()?;
+
+    let mut derived_feed_data = &data[8..];
+    let derived_feed = DerivedFeed::deserialize(&mut derived_feed_data)?;
+
+    Ok(derived_feed)
+}
+
+pub fn load_basket_feed_from_account_info(basket_account_info: &AccountInfo) -> Result<BasketFeed> {
+    let data = basket_account_info.try_borrow_data()?;
+
+    let mut basket_feed_data = &data[8..];
+    let basket_feed = BasketFeed::deserialize(&mut basket_feed_data)?;
+
+    Ok(basket_feed)
+}
+
+/// Rescales `value` from `from_decimals` to `to_decimals`, truncating when
+/// decimals are dropped. Returns `None` on overflow.
+pub fn scale_price(value: u128, from_decimals: u8, to_decimals: u8) -> Option<u128> {
+    if to_decimals >= from_decimals {
+        10u128
+            .checked_pow(u32::from(to_decimals - from_decimals))
+            .and_then(|factor| value.checked_mul(factor))
+    } else {
+        10u128
+            .checked_pow(u32::from(from_decimals - to_decimals))
+            .map(|factor| value / factor)
+    }
+}
+
+/// Accounts of an `update_price` CPI. Optional accounts left as `None` are
+/// passed as absent.
+///
+/// Pass the feed's `FeedConfig` PDA as `feed_config` (see
+/// [`find_feed_config_address`]) even if the admin has not created it, so
+/// the feed's quorum override applies once it exists.
+#[derive(Clone)]
+pub struct UpdatePriceAccounts<'info> {
+    pub oracle_state: AccountInfo<'info>,
+    pub price_feed: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub admin: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub oracle_program: AccountInfo<'info>,
+    pub operator_registry: Option<AccountInfo<'info>>,
+    pub price_history: Option<AccountInfo<'info>>,
+    pub price_accumulator: Option<AccountInfo<'info>>,
+    pub feed_registry: Option<AccountInfo<'info>>,
+    pub feed_registry_page: Option<AccountInfo<'info>>,
+    pub feed_config: Option<AccountInfo<'info>>,
+    pub secondary_oracle_state: Option<AccountInfo<'info>>,
+    pub signer_stats: Option<AccountInfo<'info>>,
+}
+
 pub fn update_price<'info>(
-    oracle_state: &AccountInfo<'info>,
-    price_feed: &AccountInfo<'info>,
-    payer: &AccountInfo<'info>,
-    admin: &AccountInfo<'info>,
-    system_program: &AccountInfo<'info>,
-    oracle_program: &AccountInfo<'info>,
+    accounts: UpdatePriceAccounts<'info>,
     feed_id: [u8; 32],
     valid_time_stamp: u128,
     observe_time_stamp: u128,
@@ -36,51 +269,1299 @@ pub fn update_price<'info>(
     signatures: Vec<[u8; 64]>,
     recovery_ids: Vec<u8>,
 ) -> Result<()> {
+    let oracle_program = &accounts.oracle_program;
     let ix = Instruction {
         program_id: *oracle_program.key,
         accounts: vec![
-            AccountMeta::new_readonly(*oracle_state.key, false),
-            AccountMeta::new(*price_feed.key, false),
-            AccountMeta::new(*payer.key, true),
-            AccountMeta::new(*admin.key, false),
-            AccountMeta::new_readonly(*system_program.key, false),
+            AccountMeta::new_readonly(*accounts.oracle_state.key, false),
+            AccountMeta::new(*accounts.price_feed.key, false),
+            AccountMeta::new(*accounts.payer.key, true),
+            AccountMeta::new(*accounts.admin.key, false),
+            AccountMeta::new_readonly(*accounts.system_program.key, false),
+            optional_account_meta(accounts.operator_registry.as_ref(), oracle_program, true),
+            optional_account_meta(accounts.price_history.as_ref(), oracle_program, true),
+            optional_account_meta(accounts.price_accumulator.as_ref(), oracle_program, true),
+            optional_account_meta(accounts.feed_registry.as_ref(), oracle_program, true),
+            optional_account_meta(accounts.feed_registry_page.as_ref(), oracle_program, true),
+            optional_account_meta(accounts.feed_config.as_ref(), oracle_program, false),
+            optional_account_meta(
+                accounts.secondary_oracle_state.as_ref(),
+                oracle_program,
+                false,
+            ),
+            optional_account_meta(accounts.signer_stats.as_ref(), oracle_program, true),
         ],
-        data: UpdatePriceArgs {
-            feed_id,
-            valid_time_stamp,
-            observe_time_stamp,
-            native_fee,
-            apro_token_fee,
-            expire_at,
-            benchmark_price,
-            ask_price,
-            bid_price,
-            config_digest,
-            epoch_and_round,
-            extra_hash,
-            signatures,
-            recovery_ids,
-        }
-        .data(),
+        data: instruction_data(
+            "update_price",
+            &UpdatePriceArgs {
+                feed_id,
+                valid_time_stamp,
+                observe_time_stamp,
+                native_fee,
+                apro_token_fee,
+                expire_at,
+                benchmark_price,
+                ask_price,
+                bid_price,
+                config_digest,
+                epoch_and_round,
+                extra_hash,
+                signatures,
+                recovery_ids,
+            },
+        ),
     };
 
-    invoke(
-        &ix,
+    let mut account_infos = vec![
+        accounts.oracle_state,
+        accounts.price_feed,
+        accounts.payer,
+        accounts.admin,
+        accounts.system_program,
+        accounts.oracle_program,
+    ];
+    account_infos.extend(accounts.operator_registry);
+    account_infos.extend(accounts.price_history);
+    account_infos.extend(accounts.price_accumulator);
+    account_infos.extend(accounts.feed_registry);
+    account_infos.extend(accounts.feed_registry_page);
+    account_infos.extend(accounts.feed_config);
+    account_infos.extend(accounts.secondary_oracle_state);
+    account_infos.extend(accounts.signer_stats);
+
+    invoke(&ix, &account_infos)?;
+
+    Ok(())
+}
+
+/// Accounts of an `update_prices` CPI. Optional accounts left as `None` are
+/// passed as absent.
+#[derive(Clone)]
+pub struct UpdatePricesAccounts<'info> {
+    pub oracle_state: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub admin: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub oracle_program: AccountInfo<'info>,
+    pub operator_registry: Option<AccountInfo<'info>>,
+    pub signer_stats: Option<AccountInfo<'info>>,
+}
+
+/// Applies a batch of reports through CPI and returns, for each report,
+/// whether it was applied. Reports held back by their feed's circuit breaker
+/// are not applied and not charged for. `feed_accounts` holds each report's
+/// `PriceFeed`, `FeedConfig`, `PriceHistory` and `PriceAccumulator` PDAs, as
+/// for [`update_prices_instruction`].
+pub fn update_prices<'info>(
+    accounts: UpdatePricesAccounts<'info>,
+    feed_accounts: &[AccountInfo<'info>],
+    reports: Vec<PriceReport>,
+) -> Result<Vec<bool>> {
+    let oracle_program_id = *accounts.oracle_program.key;
+    let ix = update_prices_instruction(
+        &oracle_program_id,
+        accounts.oracle_state.key,
+        accounts.payer.key,
+        accounts.admin.key,
+        accounts
+            .operator_registry
+            .as_ref()
+            .map(|account| account.key),
+        accounts.signer_stats.as_ref().map(|account| account.key),
+        reports,
+    );
+
+    let mut account_infos = vec![
+        accounts.oracle_state,
+        accounts.payer,
+        accounts.admin,
+        accounts.system_program,
+        accounts.oracle_program,
+    ];
+    account_infos.extend(accounts.operator_registry);
+    account_infos.extend(accounts.signer_stats);
+    account_infos.extend_from_slice(feed_accounts);
+
+    invoke(&ix, &account_infos)?;
+
+    match get_return_data() {
+        Some((program_id, data)) if program_id == oracle_program_id => {
+            Ok(Vec::<bool>::try_from_slice(&data)?)
+        }
+        _ => err!(SdkError::MissingReturnData),
+    }
+}
+
+/// Builds an `update_prices` instruction, e.g. for a relayer. The `PriceFeed`
+/// of each report must already exist; its history and accumulator are
+/// advanced if they exist.
+pub fn update_prices_instruction(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    payer: &Pubkey,
+    admin: &Pubkey,
+    operator_registry: Option<&Pubkey>,
+    signer_stats: Option<&Pubkey>,
+    reports: Vec<PriceReport>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*oracle_state, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*admin, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        match operator_registry {
+            Some(operator_registry) => AccountMeta::new(*operator_registry, false),
+            None => AccountMeta::new_readonly(*program_id, false),
+        },
+        match signer_stats {
+            Some(signer_stats) => AccountMeta::new(*signer_stats, false),
+            None => AccountMeta::new_readonly(*program_id, false),
+        },
+    ];
+    for report in &reports {
+        accounts.push(AccountMeta::new(
+            find_price_feed_address(program_id, oracle_state, &report.feed_id),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            find_feed_config_address(program_id, oracle_state, &report.feed_id),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_price_history_address(program_id, oracle_state, &report.feed_id),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_price_accumulator_address(program_id, oracle_state, &report.feed_id),
+            false,
+        ));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction_data("update_prices", &UpdatePricesArgs { reports }),
+    }
+}
+
+/// Builds a `submit_merkle_root` instruction caching a DON-signed root; see
+/// [`merkle::MerkleTree`] and [`merkle::root_signing_hash`].
+pub fn submit_merkle_root_instruction(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    payer: &Pubkey,
+    root: [u8; 32],
+    config_digest: [u8; 32],
+    epoch_and_round: u128,
+    extra_hash: [u8; 32],
+    signatures: Vec<[u8; 64]>,
+    recovery_ids: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*oracle_state, false),
+            AccountMeta::new(
+                find_merkle_root_address(program_id, oracle_state, &root),
+                false,
+            ),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(
+            "submit_merkle_root",
+            &SubmitMerkleRootArgs {
+                root,
+                config_digest,
+                epoch_and_round,
+                extra_hash,
+                signatures,
+                recovery_ids,
+            },
+        ),
+    }
+}
+
+/// Builds an `update_price_with_proof` instruction applying one report of a
+/// submitted root. The report's context and signatures are ignored; they are
+/// taken from the root. `feed_registry_page` is the registry's current page,
+/// needed when the report creates a feed under an `OracleState` with a
+/// registry.
+pub fn update_price_with_proof_instruction(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    payer: &Pubkey,
+    admin: &Pubkey,
+    root: &[u8; 32],
+    operator_registry: Option<&Pubkey>,
+    feed_registry_page: Option<&Pubkey>,
+    signer_stats: Option<&Pubkey>,
+    report: &PriceReport,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*oracle_state, false),
+            AccountMeta::new_readonly(
+                find_merkle_root_address(program_id, oracle_state, root),
+                false,
+            ),
+            AccountMeta::new(
+                find_price_feed_address(program_id, oracle_state, &report.feed_id),
+                false,
+            ),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*admin, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            match operator_registry {
+                Some(operator_registry) => AccountMeta::new(*operator_registry, false),
+                None => AccountMeta::new_readonly(*program_id, false),
+            },
+            AccountMeta::new_readonly(*program_id, false),
+            AccountMeta::new_readonly(*program_id, false),
+            match feed_registry_page {
+                Some(_) => {
+                    AccountMeta::new(find_feed_registry_address(program_id, oracle_state), false)
+                }
+                None => AccountMeta::new_readonly(*program_id, false),
+            },
+            match feed_registry_page {
+                Some(feed_registry_page) => AccountMeta::new(*feed_registry_page, false),
+                None => AccountMeta::new_readonly(*program_id, false),
+            },
+            AccountMeta::new_readonly(
+                find_feed_config_address(program_id, oracle_state, &report.feed_id),
+                false,
+            ),
+            match signer_stats {
+                Some(signer_stats) => AccountMeta::new(*signer_stats, false),
+                None => AccountMeta::new_readonly(*program_id, false),
+            },
+        ],
+        data: instruction_data(
+            "update_price_with_proof",
+            &UpdatePriceWithProofArgs {
+                feed_id: report.feed_id,
+                valid_time_stamp: report.valid_time_stamp,
+                observe_time_stamp: report.observe_time_stamp,
+                native_fee: report.native_fee,
+                apro_token_fee: report.apro_token_fee,
+                expire_at: report.expire_at,
+                benchmark_price: report.benchmark_price,
+                ask_price: report.ask_price,
+                bid_price: report.bid_price,
+                proof,
+            },
+        ),
+    }
+}
+
+pub fn find_merkle_root_address(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    root: &[u8; 32],
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"merkle_root", oracle_state.as_ref(), root.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Builds an `update_data_feed` instruction for a `Signed` or `Data` feed.
+pub fn update_data_feed_instruction(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    payer: &Pubkey,
+    admin: &Pubkey,
+    operator_registry: Option<&Pubkey>,
+    signer_stats: Option<&Pubkey>,
+    report: DataReport,
+) -> Instruction {
+    let feed_config = find_feed_config_address(program_id, oracle_state, &report.feed_id);
+    let (data_feed, _) = Pubkey::find_program_address(
+        &[b"data_feed", oracle_state.as_ref(), report.feed_id.as_ref()],
+        program_id,
+    );
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*oracle_state, false),
+            AccountMeta::new_readonly(feed_config, false),
+            AccountMeta::new(data_feed, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*admin, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            match operator_registry {
+                Some(operator_registry) => AccountMeta::new(*operator_registry, false),
+                None => AccountMeta::new_readonly(*program_id, false),
+            },
+            match signer_stats {
+                Some(signer_stats) => AccountMeta::new(*signer_stats, false),
+                None => AccountMeta::new_readonly(*program_id, false),
+            },
+        ],
+        data: instruction_data("update_data_feed", &UpdateDataFeedArgs { report }),
+    }
+}
+
+pub fn find_feed_config_address(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    feed_id: &[u8; 32],
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"feed_config", oracle_state.as_ref(), feed_id.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_feed_registry_address(program_id: &Pubkey, oracle_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"feed_registry", oracle_state.as_ref()], program_id).0
+}
+
+/// Addresses of all pages of `feed_registry`, ordered by index.
+pub fn feed_registry_page_addresses(
+    program_id: &Pubkey,
+    feed_registry: &FeedRegistry,
+) -> Vec<Pubkey> {
+    (0..feed_registry.page_count)
+        .map(|page_index| {
+            Pubkey::find_program_address(
+                &[
+                    b"feed_registry_page",
+                    feed_registry.oracle_state.as_ref(),
+                    page_index.to_le_bytes().as_ref(),
+                ],
+                program_id,
+            )
+            .0
+        })
+        .collect()
+}
+
+pub fn find_price_feed_address(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    feed_id: &[u8; 32],
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"price_feed", oracle_state.as_ref(), feed_id.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_price_history_address(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    feed_id: &[u8; 32],
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"price_history", oracle_state.as_ref(), feed_id.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+pub fn find_price_accumulator_address(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    feed_id: &[u8; 32],
+) -> Pubkey {
+    Pubkey::find_program_address(
         &[
-            oracle_state.clone(),
-            price_feed.clone(),
-            payer.clone(),
-            admin.clone(),
-            system_program.clone(),
-            oracle_program.clone(),
+            b"price_accumulator",
+            oracle_state.as_ref(),
+            feed_id.as_ref(),
         ],
-    )?;
+        program_id,
+    )
+    .0
+}
 
-    Ok(())
+pub fn find_signer_stats_address(program_id: &Pubkey, oracle_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"signer_stats", oracle_state.as_ref()], program_id).0
+}
+
+pub fn find_aggregate_feed_address(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    feed_id: &[u8; 32],
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"aggregate_feed", authority.as_ref(), feed_id.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Builds an `aggregate_feed` instruction recomputing `aggregate_feed` from
+/// its `sources` and their `feed_configs`.
+pub fn aggregate_feed_instruction(
+    program_id: &Pubkey,
+    aggregate_feed_address: &Pubkey,
+    aggregate_feed: &AggregateFeed,
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*aggregate_feed_address, false)];
+    accounts.extend(
+        aggregate_feed
+            .sources
+            .iter()
+            .zip(&aggregate_feed.feed_configs)
+            .flat_map(|(source, feed_config)| {
+                [
+                    AccountMeta::new_readonly(*source, false),
+                    AccountMeta::new_readonly(*feed_config, false),
+                ]
+            }),
+    );
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction_data("aggregate_feed", &AggregateFeedArgs {}),
+    }
+}
+
+pub fn find_derived_feed_address(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    derived_id: &[u8; 32],
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"derived_feed", oracle_state.as_ref(), derived_id.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Builds a `refresh_derived_feed` instruction for `derived_feed`.
+pub fn refresh_derived_feed_instruction(
+    program_id: &Pubkey,
+    derived_feed_address: &Pubkey,
+    derived_feed: &DerivedFeed,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*derived_feed_address, false),
+            AccountMeta::new_readonly(derived_feed.base_feed, false),
+            AccountMeta::new_readonly(derived_feed.base_feed_config, false),
+            AccountMeta::new_readonly(derived_feed.quote_feed.unwrap_or(*program_id), false),
+            AccountMeta::new_readonly(derived_feed.quote_feed_config.unwrap_or(*program_id), false),
+        ],
+        data: instruction_data("refresh_derived_feed", &RefreshDerivedFeedArgs {}),
+    }
 }
 
+pub fn find_basket_feed_address(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    basket_id: &[u8; 32],
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"basket_feed", oracle_state.as_ref(), basket_id.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Builds a `refresh_basket_feed` instruction for `basket_feed`.
+pub fn refresh_basket_feed_instruction(
+    program_id: &Pubkey,
+    basket_feed_address: &Pubkey,
+    basket_feed: &BasketFeed,
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*basket_feed_address, false)];
+    accounts.extend(basket_feed.constituents.iter().flat_map(|constituent| {
+        [
+            AccountMeta::new_readonly(constituent.price_feed, false),
+            AccountMeta::new_readonly(constituent.feed_config, false),
+        ]
+    }));
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction_data("refresh_basket_feed", &RefreshBasketFeedArgs {}),
+    }
+}
+
+/// Builds a `migrate_oracle_state` instruction moving a legacy `OracleState`
+/// to the zero-copy layout. `payer` tops up the account's rent.
+pub fn migrate_oracle_state_instruction(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*oracle_state, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("migrate_oracle_state", &MigrateOracleStateArgs {}),
+    }
+}
+
+/// Builds a `migrate_price_feed` instruction moving a legacy `PriceFeed` to
+/// the zero-copy layout. `payer` tops up the account's rent.
+pub fn migrate_price_feed_instruction(
+    program_id: &Pubkey,
+    oracle_state: &Pubkey,
+    feed_id: [u8; 32],
+    payer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*oracle_state, false),
+            AccountMeta::new(
+                find_price_feed_address(program_id, oracle_state, &feed_id),
+                false,
+            ),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("migrate_price_feed", &MigratePriceFeedArgs { feed_id }),
+    }
+}
+
+#[error_code]
+pub enum SdkError {
+    #[msg("Feed is retired")]
+    FeedRetired,
+    #[msg("Feed config belongs to another feed")]
+    FeedConfigMismatch,
+    #[msg("Oracle program returned no data")]
+    MissingReturnData,
+}
+
+/// Anchor treats an optional account whose key is the program id as absent.
+fn optional_account_meta(
+    account: Option<&AccountInfo>,
+    oracle_program: &AccountInfo,
+    is_writable: bool,
+) -> AccountMeta {
+    match account {
+        Some(account) if is_writable => AccountMeta::new(*account.key, false),
+        Some(account) => AccountMeta::new_readonly(*account.key, false),
+        None => AccountMeta::new_readonly(*oracle_program.key, false),
+    }
+}
+
+/// Mirror of the program's zero-copy `PriceFeed`, whose layout matches its
+/// Borsh encoding.
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
 pub struct PriceFeed {
+    pub feed_id: [u8; 32],
+    pub native_fee: u128,
+    pub apro_token_fee: u128,
+    pub benchmark_price: u128,
+    pub ask_price: u128,
+    pub bid_price: u128,
+    pub epoch_and_round: u128,
+    pub confidence: u128,
+    pub config_digest: [u8; 32],
+    pub extra_hash: [u8; 32],
+    pub valid_time_stamp: u64,
+    pub observe_time_stamp: u64,
+    pub expire_at: u64,
+    pub spread_bps: u64,
+    pub breaker_max_deviation_bps: u64,
+    pub breaker_max_rate_bps_per_sec: u64,
+    pub breaker_confirmation_signatures: u64,
+    pub breaker_action: u8,
+    pub breaker_state: u8,
+    /// Bit `i` is set if the oracle at index `i` of the `OracleState`
+    /// signed the stored report.
+    pub signer_bitmap: u16,
+    pub _padding: [u8; 4],
+    /// Slot and cluster time at which the stored report was applied.
+    pub last_update_slot: u64,
+    pub last_update_unix_ts: i64,
+    /// Number of reports applied to the feed.
+    pub update_count: u64,
+    /// Payer of the transaction that applied the stored report.
+    pub updater: Pubkey,
+    /// An [`UpdateReason`], see [`PriceFeed::update_reason`].
+    pub update_reason: u8,
+    /// A [`PriceSource`], see [`PriceFeed::price_source`].
+    pub price_source: u8,
+    pub _reserved: [u8; 54],
+}
+
+impl PriceFeed {
+    /// Size of a price feed account, discriminator included.
+    pub const LEN: usize = 392;
+
+    pub fn circuit_breaker(&self) -> CircuitBreaker {
+        CircuitBreaker {
+            max_deviation_bps: self.breaker_max_deviation_bps,
+            max_rate_bps_per_sec: self.breaker_max_rate_bps_per_sec,
+            action: match self.breaker_action {
+                0 => BreakerAction::Reject,
+                _ => BreakerAction::RequireConfirmation,
+            },
+            confirmation_signatures: self.breaker_confirmation_signatures,
+        }
+    }
+
+    pub fn breaker_state(&self) -> BreakerState {
+        BreakerState::from_u8(self.breaker_state)
+    }
+
+    /// Whether the circuit breaker is holding back reports; the stored price
+    /// is the last one before the move that tripped it.
+    pub fn is_breaker_tripped(&self) -> bool {
+        self.breaker_state() == BreakerState::NeedsConfirmation
+    }
+
+    pub fn update_reason(&self) -> UpdateReason {
+        UpdateReason::from_u8(self.update_reason)
+    }
+
+    /// Whether the stored price is a signed report or a fallback the admin
+    /// set during an outage.
+    pub fn price_source(&self) -> PriceSource {
+        PriceSource::from_u8(self.price_source)
+    }
+
+    /// Whether the stored price is a manual fallback past its `expire_at`,
+    /// and so should not be used.
+    pub fn is_fallback_expired(&self, now: i64) -> bool {
+        self.price_source() == PriceSource::Manual && now >= self.expire_at as i64
+    }
+
+    /// Whether the feed has gone longer than its published heartbeat
+    /// without a report, as of `now`. Always `false` for feeds without a
+    /// heartbeat or if `config` belongs to another feed.
+    pub fn is_heartbeat_violated(&self, config: &FeedConfig, now: i64) -> bool {
+        let heartbeat_seconds = config.push_policy.heartbeat_seconds;
+        config.feed_id == self.feed_id
+            && heartbeat_seconds > 0
+            && now.saturating_sub(self.valid_time_stamp as i64) > heartbeat_seconds as i64
+    }
+
+    /// Whether the stored report was applied in `slot`, e.g. to refuse a
+    /// price pushed in the same slot as the consuming transaction.
+    pub fn updated_in_slot(&self, slot: u64) -> bool {
+        self.update_count > 0 && self.last_update_slot == slot
+    }
+
+    /// Number of oracles that signed the stored report.
+    pub fn signer_count(&self) -> u32 {
+        self.signer_bitmap.count_ones()
+    }
+
+    /// `benchmark_price` together with its confidence, half the bid/ask
+    /// spread: the price lies in `price ± confidence`.
+    pub fn price_with_confidence(&self) -> (u128, u128) {
+        (self.benchmark_price, self.confidence)
+    }
+
+    /// `benchmark_price`, or `None` if the bid/ask spread is wider than
+    /// `max_spread_bps`.
+    pub fn price_with_max_spread(&self, max_spread_bps: u64) -> Option<u128> {
+        (self.spread_bps <= max_spread_bps).then_some(self.benchmark_price)
+    }
+
+    /// `benchmark_price` expressed with `target_decimals` decimals, using the
+    /// decimals published in the feed's config. Returns `None` if `config`
+    /// belongs to another feed or the value overflows.
+    pub fn scaled_benchmark_price(&self, config: &FeedConfig, target_decimals: u8) -> Option<u128> {
+        self.scaled(self.benchmark_price, config, target_decimals)
+    }
+
+    pub fn scaled_ask_price(&self, config: &FeedConfig, target_decimals: u8) -> Option<u128> {
+        self.scaled(self.ask_price, config, target_decimals)
+    }
+
+    pub fn scaled_bid_price(&self, config: &FeedConfig, target_decimals: u8) -> Option<u128> {
+        self.scaled(self.bid_price, config, target_decimals)
+    }
+
+    fn scaled(&self, value: u128, config: &FeedConfig, target_decimals: u8) -> Option<u128> {
+        if config.feed_id != self.feed_id {
+            return None;
+        }
+        scale_price(value, config.decimals, target_decimals)
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BreakerAction {
+    Reject,
+    RequireConfirmation,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BreakerState {
+    Normal,
+    NeedsConfirmation,
+    Acknowledged,
+}
+
+impl BreakerState {
+    fn from_u8(state: u8) -> Self {
+        match state {
+            0 => BreakerState::Normal,
+            1 => BreakerState::NeedsConfirmation,
+            _ => BreakerState::Acknowledged,
+        }
+    }
+}
+
+/// A borrowed view of a price feed account that reads fields in place,
+/// without deserializing or allocating. See [`load_price_feed_ref`].
+pub struct PriceFeedRef<'a> {
+    data: Ref<'a, [u8]>,
+}
+
+impl PriceFeedRef<'_> {
+    pub fn feed_id(&self) -> [u8; 32] {
+        self.bytes(0)
+    }
+
+    pub fn benchmark_price(&self) -> u128 {
+        u128::from_le_bytes(self.bytes(64))
+    }
+
+    pub fn ask_price(&self) -> u128 {
+        u128::from_le_bytes(self.bytes(80))
+    }
+
+    pub fn bid_price(&self) -> u128 {
+        u128::from_le_bytes(self.bytes(96))
+    }
+
+    pub fn epoch_and_round(&self) -> u128 {
+        u128::from_le_bytes(self.bytes(112))
+    }
+
+    pub fn confidence(&self) -> u128 {
+        u128::from_le_bytes(self.bytes(128))
+    }
+
+    pub fn valid_time_stamp(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(208))
+    }
+
+    pub fn observe_time_stamp(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(216))
+    }
+
+    pub fn expire_at(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(224))
+    }
+
+    pub fn spread_bps(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(232))
+    }
+
+    pub fn breaker_state(&self) -> BreakerState {
+        BreakerState::from_u8(self.data[265])
+    }
+
+    pub fn signer_bitmap(&self) -> u16 {
+        u16::from_le_bytes(self.bytes(266))
+    }
+
+    pub fn last_update_slot(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(272))
+    }
+
+    pub fn last_update_unix_ts(&self) -> i64 {
+        i64::from_le_bytes(self.bytes(280))
+    }
+
+    pub fn update_count(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(288))
+    }
+
+    pub fn updater(&self) -> Pubkey {
+        Pubkey::new_from_array(self.bytes(296))
+    }
+
+    pub fn update_reason(&self) -> UpdateReason {
+        UpdateReason::from_u8(self.data[328])
+    }
+
+    pub fn price_source(&self) -> PriceSource {
+        PriceSource::from_u8(self.data[329])
+    }
+
+    /// See [`PriceFeed::is_fallback_expired`].
+    pub fn is_fallback_expired(&self, now: i64) -> bool {
+        self.price_source() == PriceSource::Manual && now >= self.expire_at() as i64
+    }
+
+    /// See [`PriceFeed::updated_in_slot`].
+    pub fn updated_in_slot(&self, slot: u64) -> bool {
+        self.update_count() > 0 && self.last_update_slot() == slot
+    }
+
+    /// See [`PriceFeed::is_breaker_tripped`].
+    pub fn is_breaker_tripped(&self) -> bool {
+        self.breaker_state() == BreakerState::NeedsConfirmation
+    }
+
+    /// See [`PriceFeed::price_with_confidence`].
+    pub fn price_with_confidence(&self) -> (u128, u128) {
+        (self.benchmark_price(), self.confidence())
+    }
+
+    fn bytes<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.data[offset..offset + N].try_into().unwrap()
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitBreaker {
+    pub max_deviation_bps: u64,
+    pub max_rate_bps_per_sec: u64,
+    pub action: BreakerAction,
+    pub confirmation_signatures: u64,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceSource {
+    /// A report signed by the DON.
+    Oracle,
+    /// A fallback price set by the admin or pauser, valid until `expire_at`.
+    Manual,
+}
+
+impl PriceSource {
+    fn from_u8(source: u8) -> Self {
+        match source {
+            0 => PriceSource::Oracle,
+            _ => PriceSource::Manual,
+        }
+    }
+}
+
+/// Why a report was pushed, as judged by the feed's [`PushPolicy`].
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateReason {
+    Unscheduled,
+    Heartbeat,
+    Deviation,
+}
+
+impl UpdateReason {
+    fn from_u8(reason: u8) -> Self {
+        match reason {
+            0 => UpdateReason::Unscheduled,
+            1 => UpdateReason::Heartbeat,
+            _ => UpdateReason::Deviation,
+        }
+    }
+}
+
+/// A feed's published service level: a report at least every
+/// `heartbeat_seconds` and on every `deviation_bps` move. Zero disables a
+/// trigger.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PushPolicy {
+    pub heartbeat_seconds: u64,
+    pub deviation_bps: u64,
+    pub reject_unscheduled: bool,
+}
+
+/// Minimum spacing between accepted updates of a feed, in slots and in
+/// report seconds. Zero disables a limit.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub min_interval_slots: u64,
+    pub min_interval_seconds: u64,
+    /// Unix time until which the admin lifted the limit.
+    pub override_until: i64,
+}
+
+impl RateLimit {
+    pub fn is_overridden(&self, now: i64) -> bool {
+        now < self.override_until
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeedStatus {
+    Active,
+    Deprecated,
+    Retired,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeedKind {
+    Price,
+    Signed,
+    Data,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct FeedConfig {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub decimals: u8,
+    pub base_symbol: String,
+    pub quote_symbol: String,
+    pub description: String,
+    pub status: FeedStatus,
+    pub kind: FeedKind,
+    pub field_names: Vec<String>,
+    pub required_signatures: u64,
+    pub weight_threshold: u64,
+    pub allowed_oracles: Vec<[u8; 20]>,
+    pub secondary_oracle_state: Option<Pubkey>,
+    pub push_policy: PushPolicy,
+    pub rate_limit: RateLimit,
+    /// When a `Deprecated` feed retires, or 0 if no date is set.
+    pub sunset_at: i64,
+}
+
+impl FeedConfig {
+    /// Converts a raw feed value into a floating point number, for display.
+    pub fn ui_value(&self, value: u128) -> f64 {
+        value as f64 / 10f64.powi(i32::from(self.decimals))
+    }
+
+    /// The feed's status at `now`: a `Deprecated` feed past its sunset is
+    /// `Retired`.
+    pub fn effective_status(&self, now: i64) -> FeedStatus {
+        match self.status {
+            FeedStatus::Deprecated if self.sunset_at != 0 && now >= self.sunset_at => {
+                FeedStatus::Retired
+            }
+            status => status,
+        }
+    }
+
+    /// Seconds left before a deprecated feed retires, or `None` if no
+    /// sunset is scheduled.
+    pub fn seconds_until_sunset(&self, now: i64) -> Option<i64> {
+        (self.status == FeedStatus::Deprecated && self.sunset_at != 0)
+            .then(|| self.sunset_at.saturating_sub(now).max(0))
+    }
+}
+
+/// Latest report of a `Signed` or `Data` feed.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct DataFeed {
+    pub feed_id: [u8; 32],
+    pub valid_time_stamp: u128,
+    pub observe_time_stamp: u128,
+    pub native_fee: u128,
+    pub apro_token_fee: u128,
+    pub expire_at: u128,
+    pub values: Vec<i128>,
+    pub config_digest: [u8; 32],
+    pub epoch_and_round: u128,
+    pub extra_hash: [u8; 32],
+}
+
+impl DataFeed {
+    /// Value of the field called `name` in the feed's `config`. For a
+    /// `Signed` feed this is the single value, whatever `name` is.
+    pub fn value(&self, config: &FeedConfig, name: &str) -> Option<i128> {
+        match config.kind {
+            FeedKind::Price => None,
+            FeedKind::Signed => self.values.first().copied(),
+            FeedKind::Data => config
+                .field_names
+                .iter()
+                .position(|field| field == name)
+                .and_then(|index| self.values.get(index).copied()),
+        }
+    }
+}
+
+/// A report value with its EVM type: `int192` or `uint192`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub enum DataValue {
+    Int(i128),
+    Uint(u128),
+}
+
+/// A signed DON report for a `Signed` or `Data` feed, as passed to
+/// `update_data_feed`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DataReport {
+    pub feed_id: [u8; 32],
+    pub valid_time_stamp: u128,
+    pub observe_time_stamp: u128,
+    pub native_fee: u128,
+    pub apro_token_fee: u128,
+    pub expire_at: u128,
+    pub values: Vec<DataValue>,
+    pub config_digest: [u8; 32],
+    pub epoch_and_round: u128,
+    pub extra_hash: [u8; 32],
+    pub signatures: Vec<[u8; 64]>,
+    pub recovery_ids: Vec<u8>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct BasketConstituent {
+    pub price_feed: Pubkey,
+    pub feed_config: Pubkey,
+    /// Weight with 9 decimals.
+    pub weight: u64,
+    pub valid_time_stamp: u128,
+}
+
+/// A weighted sum of feeds; see [`refresh_basket_feed_instruction`].
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct BasketFeed {
+    pub oracle_state: Pubkey,
+    pub basket_id: [u8; 32],
+    pub constituents: Vec<BasketConstituent>,
+    pub decimals: u8,
+    pub max_staleness: u64,
+    pub value: u128,
+    pub updated_at: i64,
+}
+
+impl BasketFeed {
+    /// Seconds between each constituent's price used for `value` and `now`.
+    pub fn constituent_ages(&self, now: i64) -> Vec<u128> {
+        let now = u128::try_from(now).unwrap_or(0);
+        self.constituents
+            .iter()
+            .map(|constituent| now.saturating_sub(constituent.valid_time_stamp))
+            .collect()
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerivedFormula {
+    Invert,
+    Product,
+    Quotient,
+}
+
+/// A price computed from other feeds; see
+/// [`refresh_derived_feed_instruction`].
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct DerivedFeed {
+    pub oracle_state: Pubkey,
+    pub derived_id: [u8; 32],
+    pub formula: DerivedFormula,
+    pub base_feed: Pubkey,
+    pub base_feed_config: Pubkey,
+    pub quote_feed: Option<Pubkey>,
+    pub quote_feed_config: Option<Pubkey>,
+    pub decimals: u8,
+    pub max_staleness: u64,
+    pub price: u128,
+    pub oldest_time_stamp: u128,
+    pub updated_at: i64,
+}
+
+/// Median of a feed across several oracle states; see
+/// [`aggregate_feed_instruction`].
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct AggregateFeed {
+    pub authority: Pubkey,
+    pub feed_id: [u8; 32],
+    pub sources: Vec<Pubkey>,
+    pub feed_configs: Vec<Pubkey>,
+    pub max_staleness: u64,
+    pub min_sources: u8,
+    pub median_price: u128,
+    pub min_price: u128,
+    pub max_price: u128,
+    pub source_count: u8,
+    pub oldest_time_stamp: u128,
+    pub updated_at: i64,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct SignerActivity {
+    pub oracle: [u8; 20],
+    /// Slot of the last report the oracle validly signed, or 0 if none.
+    pub last_signed_slot: u64,
+    /// One bit per recorded report, the most recent in bit 0; a set bit
+    /// means the oracle signed it.
+    pub participation: u64,
+}
+
+impl SignerActivity {
+    /// How many of the last 64 recorded reports the oracle signed.
+    pub fn participation_count(&self) -> u32 {
+        self.participation.count_ones()
+    }
+}
+
+/// Which oracles of an `OracleState` sign its reports; see
+/// [`SignerStats::inactive_signers`].
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct SignerStats {
+    pub oracle_state: Pubkey,
+    pub report_count: u64,
+    pub signers: Vec<SignerActivity>,
+}
+
+impl SignerStats {
+    /// Oracles that have not signed a report within `max_idle_slots` of
+    /// `current_slot`, or that signed fewer than `min_participation` of the
+    /// last 64 recorded reports. Candidates for rotation.
+    pub fn inactive_signers(
+        &self,
+        current_slot: u64,
+        max_idle_slots: u64,
+        min_participation: u32,
+    ) -> Vec<[u8; 20]> {
+        self.signers
+            .iter()
+            .filter(|activity| {
+                current_slot.saturating_sub(activity.last_signed_slot) > max_idle_slots
+                    || activity.participation_count() < min_participation
+            })
+            .map(|activity| activity.oracle)
+            .collect()
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct RegisteredFeed {
+    pub feed_id: [u8; 32],
+    pub created_at: i64,
+    pub status: FeedStatus,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct FeedRegistry {
+    pub oracle_state: Pubkey,
+    pub page_count: u32,
+    pub feed_count: u64,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct FeedRegistryPage {
+    pub oracle_state: Pubkey,
+    pub page_index: u32,
+    pub feeds: Vec<RegisteredFeed>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct PriceHistoryEntry {
+    pub valid_time_stamp: u128,
+    pub benchmark_price: u128,
+    pub bid_price: u128,
+    pub ask_price: u128,
+    pub epoch_and_round: u128,
+}
+
+#[derive(Clone, Debug)]
+pub struct PriceHistory {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    /// Entries ordered from the oldest to the most recent one.
+    pub entries: Vec<PriceHistoryEntry>,
+}
+
+impl PriceHistory {
+    /// Iterates over the entries starting with the most recent one.
+    pub fn iter(&self) -> impl Iterator<Item = &PriceHistoryEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn latest(&self) -> Option<&PriceHistoryEntry> {
+        self.entries.last()
+    }
+
+    /// Returns the most recent entry whose `valid_time_stamp` is at or before
+    /// `timestamp`.
+    pub fn price_at_or_before(&self, timestamp: u128) -> Option<&PriceHistoryEntry> {
+        self.iter()
+            .find(|entry| entry.valid_time_stamp <= timestamp)
+    }
+}
+
+pub const ACCUMULATOR_OBSERVATIONS: usize = 32;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct PriceObservation {
+    pub timestamp: u128,
+    pub cumulative_price: u128,
+    pub price: u128,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct PriceAccumulator {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub last_timestamp: u128,
+    pub last_price: u128,
+    /// Running sum of `benchmark_price * seconds`. It wraps on overflow, so
+    /// only differences between two points in time are meaningful.
+    pub cumulative_price: u128,
+    /// Time-weighted EMA in the same units as `benchmark_price`. Each update
+    /// moves it towards the new price by `min(elapsed, ema_period) /
+    /// ema_period`, a first order approximation of `1 - exp(-elapsed /
+    /// ema_period)`, and truncates towards the previous value, so it lags the
+    /// exact EMA by at most one unit per update.
+    pub ema_price: u128,
+    pub ema_period: u64,
+    pub observation_head: u32,
+    pub observation_len: u32,
+    pub observations: [PriceObservation; ACCUMULATOR_OBSERVATIONS],
+}
+
+impl PriceAccumulator {
+    /// Cumulative price at `timestamp`, extrapolated from the most recent
+    /// observation at or before it. Returns `None` when `timestamp` predates
+    /// the retained observations.
+    pub fn cumulative_price_at(&self, timestamp: u128) -> Option<u128> {
+        if self.observation_len == 0 {
+            return None;
+        }
+        if timestamp >= self.last_timestamp {
+            return Some(
+                self.cumulative_price.wrapping_add(
+                    self.last_price
+                        .wrapping_mul(timestamp - self.last_timestamp),
+                ),
+            );
+        }
+
+        self.observations[..self.observation_len as usize]
+            .iter()
+            .filter(|observation| observation.timestamp <= timestamp)
+            .max_by_key(|observation| observation.timestamp)
+            .map(|observation| {
+                observation.cumulative_price.wrapping_add(
+                    observation
+                        .price
+                        .wrapping_mul(timestamp - observation.timestamp),
+                )
+            })
+    }
+
+    /// Time-weighted average of `benchmark_price` over `[now - window, now]`,
+    /// in the same units as `benchmark_price`. The result is truncated, so it
+    /// is at most one unit below the exact average. Returns `None` for an
+    /// empty window or when the window reaches further back than the retained
+    /// observations.
+    pub fn twap_at(&self, window: u128, now: u128) -> Option<u128> {
+        if window == 0 || window > now {
+            return None;
+        }
+        let end = self.cumulative_price_at(now)?;
+        let start = self.cumulative_price_at(now - window)?;
+        Some(end.wrapping_sub(start) / window)
+    }
+}
+
+/// A signed DON price report, as passed to `update_prices`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PriceReport {
     pub feed_id: [u8; 32],
     pub valid_time_stamp: u128,
     pub observe_time_stamp: u128,
@@ -93,6 +1574,17 @@ pub struct PriceFeed {
     pub config_digest: [u8; 32],
     pub epoch_and_round: u128,
     pub extra_hash: [u8; 32],
+    pub signatures: Vec<[u8; 64]>,
+    pub recovery_ids: Vec<u8>,
+}
+
+#[derive(AnchorDeserialize)]
+struct PriceHistoryHeader {
+    oracle_state: Pubkey,
+    feed_id: [u8; 32],
+    capacity: u64,
+    len: u64,
+    head: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -113,15 +1605,64 @@ struct UpdatePriceArgs {
     recovery_ids: Vec<u8>,
 }
 
-impl UpdatePriceArgs {
-    fn data(&self) -> Vec<u8> {
-        let mut data = Vec::new();
-        let preimage = "global:update_price".as_bytes();
-        let hash = hash(preimage);
-        let discriminator = &hash.to_bytes()[..8];
-        data.extend_from_slice(discriminator);
-        data.extend_from_slice(&AnchorSerialize::try_to_vec(self).unwrap());
+#[derive(AnchorSerialize)]
+struct UpdateDataFeedArgs {
+    report: DataReport,
+}
 
-        data
-    }
+#[derive(AnchorSerialize)]
+struct AggregateFeedArgs {}
+
+#[derive(AnchorSerialize)]
+struct RefreshDerivedFeedArgs {}
+
+#[derive(AnchorSerialize)]
+struct RefreshBasketFeedArgs {}
+
+#[derive(AnchorSerialize)]
+struct MigrateOracleStateArgs {}
+
+#[derive(AnchorSerialize)]
+struct MigratePriceFeedArgs {
+    feed_id: [u8; 32],
+}
+
+#[derive(AnchorSerialize)]
+struct UpdatePricesArgs {
+    reports: Vec<PriceReport>,
+}
+
+#[derive(AnchorSerialize)]
+struct SubmitMerkleRootArgs {
+    root: [u8; 32],
+    config_digest: [u8; 32],
+    epoch_and_round: u128,
+    extra_hash: [u8; 32],
+    signatures: Vec<[u8; 64]>,
+    recovery_ids: Vec<u8>,
+}
+
+#[derive(AnchorSerialize)]
+struct UpdatePriceWithProofArgs {
+    feed_id: [u8; 32],
+    valid_time_stamp: u128,
+    observe_time_stamp: u128,
+    native_fee: u128,
+    apro_token_fee: u128,
+    expire_at: u128,
+    benchmark_price: u128,
+    ask_price: u128,
+    bid_price: u128,
+    proof: Vec<[u8; 32]>,
+}
+
+fn instruction_data(name: &str, args: &impl AnchorSerialize) -> Vec<u8> {
+    let mut data = Vec::new();
+    let preimage = format!("global:{}", name);
+    let hash = hash(preimage.as_bytes());
+    let discriminator = &hash.to_bytes()[..8];
+    data.extend_from_slice(discriminator);
+    data.extend_from_slice(&AnchorSerialize::try_to_vec(args).unwrap());
+
+    data
 }
@@ -0,0 +1,112 @@
+//! Helpers for building Merkle-batched report submissions. The tree uses
+//! sorted-pair keccak256 hashing, matching the on-chain verifier and
+//! OpenZeppelin's `MerkleProof`.
+
+use crate::PriceReport;
+use anchor_lang::solana_program::keccak::hashv;
+
+pub struct MerkleTree {
+    /// `layers[0]` holds the leaves, the last layer holds the root.
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Builds a tree over `leaves`. An odd node at the end of a layer is
+    /// carried up unchanged. Returns `None` if `leaves` is empty.
+    pub fn new(leaves: Vec<[u8; 32]>) -> Option<Self> {
+        if leaves.is_empty() {
+            return None;
+        }
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+
+        Some(MerkleTree { layers })
+    }
+
+    /// Builds a tree whose leaves are the report data hashes of `reports`.
+    pub fn from_reports(reports: &[PriceReport]) -> Option<Self> {
+        Self::new(reports.iter().map(report_data_hash).collect())
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// Proof for the leaf at `index`, or `None` if out of range.
+    pub fn proof(&self, mut index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.layers[0].len() {
+            return None;
+        }
+
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+pub fn verify_proof(leaf: &[u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(*leaf, |node, sibling| hash_pair(&node, sibling));
+    computed == *root
+}
+
+/// `keccak256(abi.encode(report_data))`, the leaf of `report` in a tree.
+pub fn report_data_hash(report: &PriceReport) -> [u8; 32] {
+    hashv(&[
+        &report.feed_id,
+        &abi_uint(report.valid_time_stamp),
+        &abi_uint(report.observe_time_stamp),
+        &abi_uint(report.native_fee),
+        &abi_uint(report.apro_token_fee),
+        &abi_uint(report.expire_at),
+        &abi_uint(report.benchmark_price),
+        &abi_uint(report.ask_price),
+        &abi_uint(report.bid_price),
+    ])
+    .to_bytes()
+}
+
+/// The hash the DON signs for a root; pass its signatures to
+/// `submit_merkle_root`.
+pub fn root_signing_hash(
+    root: &[u8; 32],
+    config_digest: &[u8; 32],
+    epoch_and_round: u128,
+    extra_hash: &[u8; 32],
+) -> [u8; 32] {
+    hashv(&[root, config_digest, &abi_uint(epoch_and_round), extra_hash]).to_bytes()
+}
+
+fn abi_uint(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
@@ -0,0 +1,117 @@
+//! Checks the SDK's hard-coded view of the `PriceFeed` account against the
+//! program's zero-copy layout.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, Space};
+use apro_svm::states::PriceFeed as ProgramPriceFeed;
+use oracle_sdk::{BreakerState, PriceSource, UpdateReason};
+
+/// A program `PriceFeed` whose fields each hold a distinct value, so reading
+/// one at the wrong offset gives a wrong value.
+fn distinct_price_feed() -> ProgramPriceFeed {
+    let mut feed: ProgramPriceFeed = bytemuck::Zeroable::zeroed();
+    feed.feed_id = [1; 32];
+    feed.native_fee = 2;
+    feed.apro_token_fee = 3;
+    feed.benchmark_price = 4;
+    feed.ask_price = 5;
+    feed.bid_price = 6;
+    feed.epoch_and_round = 7;
+    feed.confidence = 8;
+    feed.config_digest = [9; 32];
+    feed.extra_hash = [10; 32];
+    feed.valid_time_stamp = 11;
+    feed.observe_time_stamp = 12;
+    feed.expire_at = 13;
+    feed.spread_bps = 14;
+    feed.breaker_max_deviation_bps = 15;
+    feed.breaker_max_rate_bps_per_sec = 16;
+    feed.breaker_confirmation_signatures = 17;
+    feed.breaker_action = 1;
+    feed.breaker_state = 1;
+    feed.signer_bitmap = 0b101;
+    feed.last_update_slot = 18;
+    feed.last_update_unix_ts = 19;
+    feed.update_count = 20;
+    feed.updater = Pubkey::new_from_array([21; 32]);
+    feed.update_reason = 2;
+    feed.price_source = 1;
+    feed
+}
+
+fn account_data(feed: &ProgramPriceFeed) -> Vec<u8> {
+    let mut data = ProgramPriceFeed::discriminator().to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(feed));
+    data
+}
+
+#[test]
+fn price_feed_len_matches_program() {
+    assert_eq!(oracle_sdk::PriceFeed::LEN, 8 + ProgramPriceFeed::INIT_SPACE);
+}
+
+#[test]
+fn price_feed_reads_match_program() {
+    let feed = distinct_price_feed();
+    let mut data = account_data(&feed);
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let account = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &apro_svm::ID,
+        false,
+        0,
+    );
+
+    let price_feed = oracle_sdk::load_price_feed_from_account_info(&account).unwrap();
+    assert_eq!(price_feed.feed_id, feed.feed_id);
+    assert_eq!(price_feed.native_fee, feed.native_fee);
+    assert_eq!(price_feed.apro_token_fee, feed.apro_token_fee);
+    assert_eq!(price_feed.benchmark_price, feed.benchmark_price);
+    assert_eq!(price_feed.ask_price, feed.ask_price);
+    assert_eq!(price_feed.bid_price, feed.bid_price);
+    assert_eq!(price_feed.epoch_and_round, feed.epoch_and_round);
+    assert_eq!(price_feed.confidence, feed.confidence);
+    assert_eq!(price_feed.config_digest, feed.config_digest);
+    assert_eq!(price_feed.extra_hash, feed.extra_hash);
+    assert_eq!(price_feed.valid_time_stamp, feed.valid_time_stamp);
+    assert_eq!(price_feed.observe_time_stamp, feed.observe_time_stamp);
+    assert_eq!(price_feed.expire_at, feed.expire_at);
+    assert_eq!(price_feed.spread_bps, feed.spread_bps);
+    assert_eq!(
+        price_feed.breaker_confirmation_signatures,
+        feed.breaker_confirmation_signatures
+    );
+    assert_eq!(price_feed.breaker_state(), BreakerState::NeedsConfirmation);
+    assert_eq!(price_feed.signer_bitmap, feed.signer_bitmap);
+    assert_eq!(price_feed.last_update_slot, feed.last_update_slot);
+    assert_eq!(price_feed.last_update_unix_ts, feed.last_update_unix_ts);
+    assert_eq!(price_feed.update_count, feed.update_count);
+    assert_eq!(price_feed.updater, feed.updater);
+    assert_eq!(price_feed.update_reason(), UpdateReason::Deviation);
+    assert_eq!(price_feed.price_source(), PriceSource::Manual);
+
+    let feed_ref = oracle_sdk::load_price_feed_ref(&account).unwrap();
+    assert_eq!(feed_ref.feed_id(), feed.feed_id);
+    assert_eq!(feed_ref.benchmark_price(), feed.benchmark_price);
+    assert_eq!(feed_ref.ask_price(), feed.ask_price);
+    assert_eq!(feed_ref.bid_price(), feed.bid_price);
+    assert_eq!(feed_ref.epoch_and_round(), feed.epoch_and_round);
+    assert_eq!(feed_ref.confidence(), feed.confidence);
+    assert_eq!(feed_ref.valid_time_stamp(), feed.valid_time_stamp);
+    assert_eq!(feed_ref.observe_time_stamp(), feed.observe_time_stamp);
+    assert_eq!(feed_ref.expire_at(), feed.expire_at);
+    assert_eq!(feed_ref.spread_bps(), feed.spread_bps);
+    assert_eq!(feed_ref.breaker_state(), BreakerState::NeedsConfirmation);
+    assert_eq!(feed_ref.signer_bitmap(), feed.signer_bitmap);
+    assert_eq!(feed_ref.last_update_slot(), feed.last_update_slot);
+    assert_eq!(feed_ref.last_update_unix_ts(), feed.last_update_unix_ts);
+    assert_eq!(feed_ref.update_count(), feed.update_count);
+    assert_eq!(feed_ref.updater(), feed.updater);
+    assert_eq!(feed_ref.update_reason(), UpdateReason::Deviation);
+    assert_eq!(feed_ref.price_source(), PriceSource::Manual);
+}
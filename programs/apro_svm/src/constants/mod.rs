@@ -1 +1,17 @@
 pub const MAX_ORACLES: usize = 10;
+pub const UPDATE_FEE_LAMPORTS: u64 = 1_000_000;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_PRICE_HISTORY_CAPACITY: u64 = 100;
+pub const ACCUMULATOR_OBSERVATIONS: usize = 32;
+pub const MAX_EMA_PERIOD: u64 = 30 * 24 * 60 * 60;
+pub const MAX_SYMBOL_LEN: usize = 16;
+pub const MAX_DESCRIPTION_LEN: usize = 64;
+pub const MAX_DECIMALS: u8 = 38;
+pub const MERKLE_ROOT_TTL: i64 = 300;
+pub const FEEDS_PER_REGISTRY_PAGE: usize = 64;
+pub const MAX_DATA_FIELDS: usize = 8;
+pub const MAX_FIELD_NAME_LEN: usize = 16;
+pub const MAX_AGGREGATE_SOURCES: usize = 8;
+pub const MAX_BASKET_CONSTITUENTS: usize = 16;
+pub const BASKET_WEIGHT_DECIMALS: u8 = 9;
+pub const MAX_FALLBACK_DURATION: i64 = 60 * 60;
@@ -17,4 +17,132 @@ pub enum ErrorCode {
     SignatureVerificationFailed,
     #[msg("Insufficient valid signatures")]
     InsufficientValidSignatures,
+    #[msg("Oracle is not part of the oracle set")]
+    OracleNotFound,
+    #[msg("Operator fee share must not exceed 10000 bps")]
+    InvalidOperatorFeeShare,
+    #[msg("Operator is not registered")]
+    OperatorNotFound,
+    #[msg("Signer is not the operator payout account")]
+    UnauthorizedOperator,
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Invalid price history capacity")]
+    InvalidHistoryCapacity,
+    #[msg("Invalid EMA period")]
+    InvalidEmaPeriod,
+    #[msg("Decimals exceed the supported maximum")]
+    InvalidDecimals,
+    #[msg("Symbol is too long")]
+    SymbolTooLong,
+    #[msg("Description is too long")]
+    DescriptionTooLong,
+    #[msg("Batch contains no reports")]
+    EmptyBatch,
+    #[msg("Price feed account does not match the report")]
+    PriceFeedAccountMismatch,
+    #[msg("Merkle root has expired")]
+    MerkleRootExpired,
+    #[msg("Merkle root has not expired yet")]
+    MerkleRootNotExpired,
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Feed registry accounts are required to create a feed")]
+    FeedRegistryRequired,
+    #[msg("Feed registry page is not the current page")]
+    InvalidFeedRegistryPage,
+    #[msg("Feed registry page is full")]
+    FeedRegistryPageFull,
+    #[msg("Feed registry page is not full yet")]
+    FeedRegistryPageNotFull,
+    #[msg("Feed must be registered with a feed config before it can be created")]
+    UnregisteredFeed,
+    #[msg("Invalid field names for the feed kind")]
+    InvalidFeedFields,
+    #[msg("Feed kind does not accept this update")]
+    FeedKindMismatch,
+    #[msg("Report values do not match the feed's fields")]
+    FieldCountMismatch,
+    #[msg("Report value does not fit in an i128")]
+    ValueOutOfRange,
+    #[msg("Bid, benchmark and ask prices are out of order")]
+    InvalidPriceOrdering,
+    #[msg("Price ordering tolerance must not exceed 10000 bps")]
+    InvalidOrderingTolerance,
+    #[msg("Invalid circuit breaker settings")]
+    InvalidCircuitBreaker,
+    #[msg("Circuit breaker is not awaiting confirmation")]
+    CircuitBreakerNotTripped,
+    #[msg("Invalid feed quorum")]
+    InvalidFeedQuorum,
+    #[msg("Oracle weight must be positive")]
+    InvalidOracleWeight,
+    #[msg("Weight threshold must be positive")]
+    InvalidWeightThreshold,
+    #[msg("Insufficient signature weight")]
+    InsufficientSignatureWeight,
+    #[msg("Secondary oracle state must differ from the feed's oracle state")]
+    InvalidSecondaryOracleState,
+    #[msg("Feed requires its secondary oracle state account")]
+    SecondaryOracleStateRequired,
+    #[msg("Primary oracle state quorum not met")]
+    PrimaryQuorumNotMet,
+    #[msg("Secondary oracle state quorum not met")]
+    SecondaryQuorumNotMet,
+    #[msg("Feed requires dual attestation through update_price")]
+    DualAttestationUnsupported,
+    #[msg("Invalid aggregate feed sources")]
+    InvalidAggregateSources,
+    #[msg("Aggregate feed source account mismatch")]
+    AggregateSourceMismatch,
+    #[msg("Not enough fresh sources to aggregate")]
+    InsufficientAggregateSources,
+    #[msg("Invalid derived feed definition")]
+    InvalidDerivedFeed,
+    #[msg("Derived feed input account mismatch")]
+    DerivedInputMismatch,
+    #[msg("Derived feed input has a zero price")]
+    InvalidDerivedInput,
+    #[msg("Derived feed input is stale")]
+    StaleDerivedInput,
+    #[msg("Invalid basket feed definition")]
+    InvalidBasketFeed,
+    #[msg("Basket constituent account mismatch")]
+    BasketConstituentMismatch,
+    #[msg("Basket constituent is stale")]
+    StaleBasketConstituent,
+    #[msg("Account must be migrated to the zero-copy layout")]
+    AccountNotMigrated,
+    #[msg("Account is not a legacy layout to migrate")]
+    InvalidLegacyAccount,
+    #[msg("Invalid push policy")]
+    InvalidPushPolicy,
+    #[msg("Update is neither a heartbeat nor a deviation")]
+    UpdateNotDue,
+    #[msg("Feed was updated too recently")]
+    UpdateTooFrequent,
+    #[msg("Fallback price duration must be positive and at most an hour")]
+    InvalidFallbackDuration,
+    #[msg("Fallback price must be non-zero and target an existing feed")]
+    InvalidFallbackPrice,
+    #[msg("Feed is retired")]
+    FeedRetired,
+    #[msg("Sunset must be in the future")]
+    InvalidSunset,
+    #[msg("Report is not newer than the feed's latest")]
+    StaleReport,
+    #[msg("Weight threshold exceeds the oracles' total weight")]
+    UnreachableWeightThreshold,
+    #[msg("Source feed holds a fallback price, is held by its circuit breaker or is retired")]
+    UnusableSourceFeed,
+    #[msg("Source feed has no FeedConfig to take its decimals from")]
+    MissingSourceDecimals,
+    #[msg("Signer stats account is required to record the report's signers")]
+    SignerStatsRequired,
+    #[msg("Signer is neither the admin nor the pauser")]
+    UnauthorizedPauser,
+    #[msg("Feed is not in the registry page")]
+    FeedNotRegistered,
 }
@@ -0,0 +1,51 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::require_migrated;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CircuitBreakerAcknowledged {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct AcknowledgeCircuitBreaker<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"price_feed",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_feed: AccountLoader<'info, PriceFeed>,
+    pub admin: Signer<'info>,
+}
+
+/// Accepts the move that tripped the breaker: the next report is applied
+/// without deviation checks and the feed returns to normal.
+pub fn handler(ctx: Context<AcknowledgeCircuitBreaker>, feed_id: [u8; 32]) -> Result<()> {
+    require_migrated::<PriceFeed>(ctx.accounts.price_feed.as_ref())?;
+    let price_feed = &mut ctx.accounts.price_feed.load_mut()?;
+    require!(
+        price_feed.breaker_state() == BreakerState::NeedsConfirmation,
+        ErrorCode::CircuitBreakerNotTripped
+    );
+
+    price_feed.set_breaker_state(BreakerState::Acknowledged);
+
+    emit!(CircuitBreakerAcknowledged {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+    });
+
+    Ok(())
+}
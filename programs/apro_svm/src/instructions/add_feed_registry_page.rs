@@ -0,0 +1,68 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FeedRegistryPageAdded {
+    pub oracle_state: Pubkey,
+    pub page_index: u32,
+}
+
+#[derive(Accounts)]
+pub struct AddFeedRegistryPage<'info> {
+    #[account(constraint = oracle_state.is_migrated() @ ErrorCode::AccountNotMigrated)]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"feed_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(
+        seeds = [
+            b"feed_registry_page",
+            oracle_state.key().as_ref(),
+            (feed_registry.page_count - 1).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub last_page: Account<'info, FeedRegistryPage>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeedRegistryPage::INIT_SPACE,
+        seeds = [
+            b"feed_registry_page",
+            oracle_state.key().as_ref(),
+            feed_registry.page_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub new_page: Account<'info, FeedRegistryPage>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the next registry page once the current one is full. Anyone may
+/// call it, so a relayer can unblock feed creation without the admin.
+pub fn handler(ctx: Context<AddFeedRegistryPage>) -> Result<()> {
+    require!(
+        ctx.accounts.last_page.is_full(),
+        ErrorCode::FeedRegistryPageNotFull
+    );
+
+    let oracle_state = ctx.accounts.oracle_state.key();
+    let page_index = ctx.accounts.feed_registry.page_count;
+
+    ctx.accounts.new_page.initialize(oracle_state, page_index);
+    ctx.accounts.feed_registry.page_count += 1;
+
+    emit!(FeedRegistryPageAdded {
+        oracle_state,
+        page_index,
+    });
+
+    Ok(())
+}
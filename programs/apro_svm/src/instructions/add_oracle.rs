@@ -1,6 +1,7 @@
 use crate::constants::MAX_ORACLES;
 use crate::errors::ErrorCode;
 use crate::states::*;
+use crate::utils::*;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -14,16 +15,16 @@ pub struct OracleAdded {
 pub struct AddOracle<'info> {
     #[account(
         mut,
-        has_one = admin @ ErrorCode::UnauthorizedAdmin,
-        constraint = oracle_state.total_oracles < MAX_ORACLES as u64 @ ErrorCode::MaxOraclesReached,
-        constraint = !oracle_state.oracles.contains(&oracle) @ ErrorCode::OracleAlreadyExists
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin,
+        constraint = oracle_state.load()?.total_oracles < MAX_ORACLES as u64 @ ErrorCode::MaxOraclesReached,
+        constraint = !oracle_state.load()?.oracles().contains(&oracle) @ ErrorCode::OracleAlreadyExists
     )]
-    pub oracle_state: Account<'info, OracleState>,
+    pub oracle_state: AccountLoader<'info, OracleState>,
     pub admin: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<AddOracle>, oracle: [u8; 20]) -> Result<()> {
-    let oracle_state = &mut ctx.accounts.oracle_state;
+    let oracle_state = &mut ctx.accounts.oracle_state.load_mut()?;
     oracle_state.add_oracle(oracle)?;
     emit!(OracleAdded {
         oracle,
@@ -0,0 +1,37 @@
+use crate::constants::MAX_ORACLES;
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct WeightedOracleAdded {
+    pub oracle: [u8; 20],
+    pub weight: u64,
+    pub total_oracles: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle: [u8; 20])]
+pub struct AddWeightedOracle<'info> {
+    #[account(
+        mut,
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin,
+        constraint = oracle_state.load()?.total_oracles < MAX_ORACLES as u64 @ ErrorCode::MaxOraclesReached,
+        constraint = !oracle_state.load()?.oracles().contains(&oracle) @ ErrorCode::OracleAlreadyExists
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AddWeightedOracle>, oracle: [u8; 20], weight: u64) -> Result<()> {
+    let oracle_state = &mut ctx.accounts.oracle_state.load_mut()?;
+    oracle_state.add_weighted_oracle(oracle, weight)?;
+    emit!(WeightedOracleAdded {
+        oracle,
+        weight,
+        total_oracles: oracle_state.total_oracles,
+    });
+
+    Ok(())
+}
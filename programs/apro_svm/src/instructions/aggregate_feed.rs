@@ -0,0 +1,86 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::source_price;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct AggregateFeedUpdated {
+    pub aggregate_feed: Pubkey,
+    pub feed_id: [u8; 32],
+    pub median_price: u128,
+    pub min_price: u128,
+    pub max_price: u128,
+    pub source_count: u8,
+    pub oldest_time_stamp: u128,
+}
+
+#[derive(Accounts)]
+pub struct AggregateFeedPrices<'info> {
+    #[account(mut)]
+    pub aggregate_feed: Account<'info, AggregateFeed>,
+}
+
+/// Recomputes an aggregate feed. Anyone may call it. `remaining_accounts`
+/// are each source's `PriceFeed` followed by its `FeedConfig`, in order.
+/// Sources whose feed does not exist yet, has not been migrated, has no
+/// `source_price` or is older than `max_staleness` are left out.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AggregateFeedPrices<'info>>,
+) -> Result<()> {
+    let aggregate_feed = &mut ctx.accounts.aggregate_feed;
+    require!(
+        ctx.remaining_accounts.len() == 2 * aggregate_feed.sources.len(),
+        ErrorCode::AggregateSourceMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut prices = Vec::with_capacity(aggregate_feed.sources.len());
+    for ((source, source_config), accounts) in aggregate_feed
+        .sources
+        .iter()
+        .zip(&aggregate_feed.feed_configs)
+        .zip(ctx.remaining_accounts.chunks(2))
+    {
+        require_keys_eq!(
+            accounts[0].key(),
+            *source,
+            ErrorCode::AggregateSourceMismatch
+        );
+        require_keys_eq!(
+            accounts[1].key(),
+            *source_config,
+            ErrorCode::AggregateSourceMismatch
+        );
+        if accounts[0].owner != &crate::ID || accounts[0].data_len() != PriceFeed::LEN {
+            continue;
+        }
+
+        let price_feed_loader = AccountLoader::<PriceFeed>::try_from(&accounts[0])?;
+        let price_feed = price_feed_loader.load()?;
+        let feed_config = FeedConfig::load(&accounts[1])?;
+        let Some(source_price) = source_price(&price_feed, feed_config.as_ref(), now) else {
+            continue;
+        };
+
+        let age = u128::try_from(now)
+            .unwrap_or(0)
+            .saturating_sub(source_price.valid_time_stamp);
+        if age <= u128::from(aggregate_feed.max_staleness) {
+            prices.push((source_price.benchmark_price, source_price.valid_time_stamp));
+        }
+    }
+
+    aggregate_feed.record(prices, now)?;
+
+    emit!(AggregateFeedUpdated {
+        aggregate_feed: aggregate_feed.key(),
+        feed_id: aggregate_feed.feed_id,
+        median_price: aggregate_feed.median_price,
+        min_price: aggregate_feed.min_price,
+        max_price: aggregate_feed.max_price,
+        source_count: aggregate_feed.source_count,
+        oldest_time_stamp: aggregate_feed.oldest_time_stamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RewardsClaimed {
+    pub oracle_state: Pubkey,
+    pub oracle: [u8; 20],
+    pub payout: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(constraint = oracle_state.is_migrated() @ ErrorCode::AccountNotMigrated)]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"operator_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub operator_registry: Account<'info, OperatorRegistry>,
+    #[account(mut)]
+    pub payout: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClaimRewards>, oracle: [u8; 20]) -> Result<()> {
+    let payout = ctx.accounts.payout.key();
+    let amount = ctx
+        .accounts
+        .operator_registry
+        .take_rewards(oracle, payout)?;
+
+    // The registry is owned by this program, so lamports can be moved out of
+    // it directly. Accrued rewards are always held on top of its rent reserve.
+    **ctx
+        .accounts
+        .operator_registry
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.payout.try_borrow_mut_lamports()? += amount;
+
+    emit!(RewardsClaimed {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        oracle,
+        payout,
+        amount,
+    });
+
+    Ok(())
+}
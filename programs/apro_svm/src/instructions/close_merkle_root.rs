@@ -0,0 +1,28 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseMerkleRoot<'info> {
+    #[account(
+        mut,
+        has_one = payer,
+        close = payer
+    )]
+    pub merkle_root: Account<'info, MerkleRoot>,
+    /// CHECK: Receives the rent back; must match the account that paid for the root.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+/// Closes an expired Merkle root and refunds its rent. Anyone may call it.
+pub fn handler(ctx: Context<CloseMerkleRoot>) -> Result<()> {
+    require!(
+        ctx.accounts
+            .merkle_root
+            .is_expired(Clock::get()?.unix_timestamp),
+        ErrorCode::MerkleRootNotExpired
+    );
+
+    Ok(())
+}
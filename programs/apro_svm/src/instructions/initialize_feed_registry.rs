@@ -0,0 +1,53 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FeedRegistryInitialized {
+    pub oracle_state: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeedRegistry<'info> {
+    #[account(mut, constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin)]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeedRegistry::INIT_SPACE,
+        seeds = [b"feed_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub feed_registry: Account<'info, FeedRegistry>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeedRegistryPage::INIT_SPACE,
+        seeds = [
+            b"feed_registry_page",
+            oracle_state.key().as_ref(),
+            0u32.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub feed_registry_page: Account<'info, FeedRegistryPage>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the registry with its first page. From then on `update_price`
+/// records every feed it creates.
+pub fn handler(ctx: Context<InitializeFeedRegistry>) -> Result<()> {
+    let oracle_state = ctx.accounts.oracle_state.key();
+
+    ctx.accounts.feed_registry.initialize(oracle_state);
+    ctx.accounts.feed_registry_page.initialize(oracle_state, 0);
+    ctx.accounts.oracle_state.load_mut()?.feed_registry_enabled = 1;
+
+    emit!(FeedRegistryInitialized { oracle_state });
+
+    Ok(())
+}
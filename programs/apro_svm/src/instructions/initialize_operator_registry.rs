@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct OperatorRegistryInitialized {
+    pub oracle_state: Pubkey,
+    pub operator_fee_bps: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOperatorRegistry<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OperatorRegistry::INIT_SPACE,
+        seeds = [b"operator_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub operator_registry: Account<'info, OperatorRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeOperatorRegistry>, operator_fee_bps: u64) -> Result<()> {
+    let oracle_state = ctx.accounts.oracle_state.key();
+    ctx.accounts
+        .operator_registry
+        .initialize(oracle_state, operator_fee_bps)?;
+
+    emit!(OperatorRegistryInitialized {
+        oracle_state,
+        operator_fee_bps,
+    });
+
+    Ok(())
+}
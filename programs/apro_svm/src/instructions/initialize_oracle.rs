@@ -15,11 +15,11 @@ pub struct InitializeOracle<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 8 + 8 + 8 + 32 + 32 * 20 + 8,
+        space = OracleState::LEN,
         seeds = [b"oracle_state", id.to_le_bytes().as_ref()],
         bump
     )]
-    pub oracle_state: Account<'info, OracleState>,
+    pub oracle_state: AccountLoader<'info, OracleState>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub admin: Signer<'info>,
@@ -32,7 +32,7 @@ pub fn handler(
     required_signatures: u64,
     expiration_period: i64,
 ) -> Result<()> {
-    let oracle_state = &mut ctx.accounts.oracle_state;
+    let oracle_state = &mut ctx.accounts.oracle_state.load_init()?;
     oracle_state.initialize(
         id,
         required_signatures,
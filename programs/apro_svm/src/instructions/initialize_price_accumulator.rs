@@ -0,0 +1,60 @@
+use crate::constants::MAX_EMA_PERIOD;
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PriceAccumulatorInitialized {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub ema_period: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct InitializePriceAccumulator<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = PriceAccumulator::LEN,
+        seeds = [
+            b"price_accumulator",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_accumulator: AccountLoader<'info, PriceAccumulator>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializePriceAccumulator>,
+    feed_id: [u8; 32],
+    ema_period: u64,
+) -> Result<()> {
+    require!(
+        ema_period > 0 && ema_period <= MAX_EMA_PERIOD,
+        ErrorCode::InvalidEmaPeriod
+    );
+
+    let oracle_state = ctx.accounts.oracle_state.key();
+    let mut price_accumulator = ctx.accounts.price_accumulator.load_init()?;
+    price_accumulator.initialize(oracle_state, feed_id, ema_period);
+
+    emit!(PriceAccumulatorInitialized {
+        oracle_state,
+        feed_id,
+        ema_period,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::constants::MAX_PRICE_HISTORY_CAPACITY;
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PriceHistoryInitialized {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub capacity: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32], capacity: u64)]
+pub struct InitializePriceHistory<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = PriceHistory::space(capacity),
+        seeds = [
+            b"price_history",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializePriceHistory>,
+    feed_id: [u8; 32],
+    capacity: u64,
+) -> Result<()> {
+    require!(
+        capacity > 0 && capacity <= MAX_PRICE_HISTORY_CAPACITY,
+        ErrorCode::InvalidHistoryCapacity
+    );
+
+    let oracle_state = ctx.accounts.oracle_state.key();
+    let mut price_history = ctx.accounts.price_history.load_init()?;
+    price_history.initialize(oracle_state, feed_id, capacity);
+
+    emit!(PriceHistoryInitialized {
+        oracle_state,
+        feed_id,
+        capacity,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SignerStatsInitialized {
+    pub oracle_state: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSignerStats<'info> {
+    #[account(
+        mut,
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SignerStats::INIT_SPACE,
+        seeds = [b"signer_stats", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub signer_stats: Account<'info, SignerStats>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the account that tracks which oracles sign the reports verified
+/// under `oracle_state`. Updates must pass it from then on.
+pub fn handler(ctx: Context<InitializeSignerStats>) -> Result<()> {
+    let oracle_state = ctx.accounts.oracle_state.key();
+    ctx.accounts.signer_stats.initialize(oracle_state);
+    ctx.accounts.oracle_state.load_mut()?.signer_stats_enabled = 1;
+
+    emit!(SignerStatsInitialized { oracle_state });
+
+    Ok(())
+}
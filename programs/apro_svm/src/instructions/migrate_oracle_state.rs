@@ -0,0 +1,54 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct OracleStateMigrated {
+    pub oracle_state: Pubkey,
+    pub id: u64,
+}
+
+#[derive(Accounts)]
+pub struct MigrateOracleState<'info> {
+    /// CHECK: An `OracleState` in its legacy Borsh layout, checked by
+    /// `read_legacy` and against its PDA.
+    #[account(mut)]
+    pub oracle_state: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Rewrites a legacy `OracleState` in the zero-copy layout, growing the
+/// account and topping up its rent from the payer. Anyone may migrate an
+/// account; its contents are carried over unchanged.
+pub fn handler(ctx: Context<MigrateOracleState>) -> Result<()> {
+    let oracle_state = ctx.accounts.oracle_state.to_account_info();
+    let data = read_legacy::<OracleState>(&oracle_state, OracleState::LEGACY_LEN)?;
+    let legacy = LegacyOracleState::deserialize(&mut data.as_slice())?;
+
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"oracle_state", legacy.id.to_le_bytes().as_ref()],
+        ctx.program_id,
+    );
+    require_keys_eq!(
+        oracle_state.key(),
+        expected,
+        ErrorCode::InvalidLegacyAccount
+    );
+
+    write_zero_copy(
+        &oracle_state,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        |state: &mut OracleState| state.migrate(&legacy),
+    )?;
+
+    emit!(OracleStateMigrated {
+        oracle_state: oracle_state.key(),
+        id: legacy.id,
+    });
+
+    Ok(())
+}
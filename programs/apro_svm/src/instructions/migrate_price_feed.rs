@@ -0,0 +1,55 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PriceFeedMigrated {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct MigratePriceFeed<'info> {
+    #[account(constraint = oracle_state.is_migrated() @ ErrorCode::AccountNotMigrated)]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    /// CHECK: A `PriceFeed` in an older layout, checked by `read_legacy`.
+    #[account(
+        mut,
+        seeds = [
+            b"price_feed",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_feed: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Rewrites a legacy `PriceFeed` in the zero-copy layout, resizing the
+/// account and topping up its rent from the payer. Anyone may migrate an
+/// account; its contents are carried over unchanged, except timestamps that
+/// do not fit the current layout, which fail the migration.
+pub fn handler(ctx: Context<MigratePriceFeed>, feed_id: [u8; 32]) -> Result<()> {
+    let price_feed = ctx.accounts.price_feed.to_account_info();
+    let data = read_legacy::<PriceFeed>(&price_feed, LegacyPriceFeed::LEN)?;
+    let legacy = LegacyPriceFeed::deserialize(&mut data.as_slice())?;
+
+    write_zero_copy(
+        &price_feed,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        |feed: &mut PriceFeed| feed.migrate(&legacy),
+    )?;
+
+    emit!(PriceFeedMigrated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+    });
+
+    Ok(())
+}
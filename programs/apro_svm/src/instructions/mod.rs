@@ -1,9 +1,191 @@
+pub mod acknowledge_circuit_breaker;
+pub mod add_feed_registry_page;
 pub mod add_oracle;
+pub mod add_weighted_oracle;
+pub mod aggregate_feed;
+pub mod claim_rewards;
+pub mod close_merkle_root;
+pub mod initialize_feed_registry;
+pub mod initialize_operator_registry;
 pub mod initialize_oracle;
+pub mod initialize_price_accumulator;
+pub mod initialize_price_history;
+pub mod initialize_signer_stats;
+pub mod migrate_oracle_state;
+pub mod migrate_price_feed;
+pub mod override_rate_limit;
+pub mod refresh_basket_feed;
+pub mod refresh_derived_feed;
+pub mod rotate_oracle;
+pub mod set_aggregate_feed;
+pub mod set_basket_feed;
+pub mod set_circuit_breaker;
+pub mod set_derived_feed;
+pub mod set_fallback_price;
+pub mod set_feed_config;
+pub mod set_feed_kind;
+pub mod set_feed_quorum;
+pub mod set_feed_status;
+pub mod set_feed_sunset;
+pub mod set_operator;
+pub mod set_operator_fee_share;
+pub mod set_oracle_weight;
+pub mod set_pauser;
+pub mod set_price_ordering_tolerance;
+pub mod set_push_policy;
+pub mod set_quorum_mode;
+pub mod set_rate_limit;
+pub mod set_registered_feeds_only;
+pub mod set_secondary_oracle_state;
+pub mod submit_merkle_root;
 pub mod update_admin;
+pub mod update_data_feed;
 pub mod update_price;
+pub mod update_price_with_proof;
+pub mod update_prices;
 
-pub use add_oracle::*;
-pub use initialize_oracle::*;
-pub use update_admin::*;
-pub use update_price::*;
+pub use acknowledge_circuit_breaker::{AcknowledgeCircuitBreaker, CircuitBreakerAcknowledged};
+pub use add_feed_registry_page::{AddFeedRegistryPage, FeedRegistryPageAdded};
+pub use add_oracle::{AddOracle, OracleAdded};
+pub use add_weighted_oracle::{AddWeightedOracle, WeightedOracleAdded};
+pub use aggregate_feed::{AggregateFeedPrices, AggregateFeedUpdated};
+pub use claim_rewards::{ClaimRewards, RewardsClaimed};
+pub use close_merkle_root::CloseMerkleRoot;
+pub use initialize_feed_registry::{FeedRegistryInitialized, InitializeFeedRegistry};
+pub use initialize_operator_registry::{InitializeOperatorRegistry, OperatorRegistryInitialized};
+pub use initialize_oracle::{InitializeOracle, OracleInitialized};
+pub use initialize_price_accumulator::{InitializePriceAccumulator, PriceAccumulatorInitialized};
+pub use initialize_price_history::{InitializePriceHistory, PriceHistoryInitialized};
+pub use initialize_signer_stats::{InitializeSignerStats, SignerStatsInitialized};
+pub use migrate_oracle_state::{MigrateOracleState, OracleStateMigrated};
+pub use migrate_price_feed::{MigratePriceFeed, PriceFeedMigrated};
+pub use override_rate_limit::{OverrideRateLimit, RateLimitOverridden};
+pub use refresh_basket_feed::{BasketFeedRefreshed, RefreshBasketFeed};
+pub use refresh_derived_feed::{DerivedFeedRefreshed, RefreshDerivedFeed};
+pub use rotate_oracle::{OracleRotated, RotateOracle};
+pub use set_aggregate_feed::{AggregateFeedConfigured, SetAggregateFeed};
+pub use set_basket_feed::{BasketFeedConfigured, SetBasketFeed};
+pub use set_circuit_breaker::{CircuitBreakerConfigured, SetCircuitBreaker};
+pub use set_derived_feed::{DerivedFeedConfigured, SetDerivedFeed};
+pub use set_fallback_price::{FallbackPriceSet, SetFallbackPrice};
+pub use set_feed_config::{FeedConfigUpdated, SetFeedConfig};
+pub use set_feed_kind::{FeedKindUpdated, SetFeedKind};
+pub use set_feed_quorum::{FeedQuorumUpdated, SetFeedQuorum};
+pub use set_feed_status::{FeedStatusUpdated, SetFeedStatus};
+pub use set_feed_sunset::SetFeedSunset;
+pub use set_operator::{OperatorSet, SetOperator};
+pub use set_operator_fee_share::{OperatorFeeShareUpdated, SetOperatorFeeShare};
+pub use set_oracle_weight::{OracleWeightUpdated, SetOracleWeight};
+pub use set_pauser::{PauserUpdated, SetPauser};
+pub use set_price_ordering_tolerance::{PriceOrderingToleranceUpdated, SetPriceOrderingTolerance};
+pub use set_push_policy::{PushPolicyUpdated, SetPushPolicy};
+pub use set_quorum_mode::{QuorumModeUpdated, SetQuorumMode};
+pub use set_rate_limit::{RateLimitUpdated, SetRateLimit};
+pub use set_registered_feeds_only::{RegisteredFeedsOnlyUpdated, SetRegisteredFeedsOnly};
+pub use set_secondary_oracle_state::{SecondaryOracleStateUpdated, SetSecondaryOracleState};
+pub use submit_merkle_root::{MerkleRootSubmitted, SubmitMerkleRoot};
+pub use update_admin::{AdminUpdated, UpdateAdmin};
+pub use update_data_feed::{DataFeedUpdated, UpdateDataFeed};
+pub use update_price::{
+    CircuitBreakerReset, CircuitBreakerTripped, FallbackPriceCleared, PriceUpdated, UpdatePrice,
+};
+pub use update_price_with_proof::UpdatePriceWithProof;
+pub use update_prices::UpdatePrices;
+
+// The modules `#[derive(Accounts)]` generates, which `#[program]` looks up
+// at the crate root.
+pub(crate) use {
+    acknowledge_circuit_breaker::__client_accounts_acknowledge_circuit_breaker,
+    add_feed_registry_page::__client_accounts_add_feed_registry_page,
+    add_oracle::__client_accounts_add_oracle,
+    add_weighted_oracle::__client_accounts_add_weighted_oracle,
+    aggregate_feed::__client_accounts_aggregate_feed_prices,
+    claim_rewards::__client_accounts_claim_rewards,
+    close_merkle_root::__client_accounts_close_merkle_root,
+    initialize_feed_registry::__client_accounts_initialize_feed_registry,
+    initialize_operator_registry::__client_accounts_initialize_operator_registry,
+    initialize_oracle::__client_accounts_initialize_oracle,
+    initialize_price_accumulator::__client_accounts_initialize_price_accumulator,
+    initialize_price_history::__client_accounts_initialize_price_history,
+    initialize_signer_stats::__client_accounts_initialize_signer_stats,
+    migrate_oracle_state::__client_accounts_migrate_oracle_state,
+    migrate_price_feed::__client_accounts_migrate_price_feed,
+    override_rate_limit::__client_accounts_override_rate_limit,
+    refresh_basket_feed::__client_accounts_refresh_basket_feed,
+    refresh_derived_feed::__client_accounts_refresh_derived_feed,
+    rotate_oracle::__client_accounts_rotate_oracle,
+    set_aggregate_feed::__client_accounts_set_aggregate_feed,
+    set_basket_feed::__client_accounts_set_basket_feed,
+    set_circuit_breaker::__client_accounts_set_circuit_breaker,
+    set_derived_feed::__client_accounts_set_derived_feed,
+    set_fallback_price::__client_accounts_set_fallback_price,
+    set_feed_config::__client_accounts_set_feed_config,
+    set_feed_kind::__client_accounts_set_feed_kind,
+    set_feed_quorum::__client_accounts_set_feed_quorum,
+    set_feed_status::__client_accounts_set_feed_status,
+    set_feed_sunset::__client_accounts_set_feed_sunset,
+    set_operator::__client_accounts_set_operator,
+    set_operator_fee_share::__client_accounts_set_operator_fee_share,
+    set_oracle_weight::__client_accounts_set_oracle_weight,
+    set_pauser::__client_accounts_set_pauser,
+    set_price_ordering_tolerance::__client_accounts_set_price_ordering_tolerance,
+    set_push_policy::__client_accounts_set_push_policy,
+    set_quorum_mode::__client_accounts_set_quorum_mode,
+    set_rate_limit::__client_accounts_set_rate_limit,
+    set_registered_feeds_only::__client_accounts_set_registered_feeds_only,
+    set_secondary_oracle_state::__client_accounts_set_secondary_oracle_state,
+    submit_merkle_root::__client_accounts_submit_merkle_root,
+    update_admin::__client_accounts_update_admin,
+    update_data_feed::__client_accounts_update_data_feed,
+    update_price::__client_accounts_update_price,
+    update_price_with_proof::__client_accounts_update_price_with_proof,
+    update_prices::__client_accounts_update_prices,
+};
+#[cfg(feature = "cpi")]
+pub(crate) use {
+    acknowledge_circuit_breaker::__cpi_client_accounts_acknowledge_circuit_breaker,
+    add_feed_registry_page::__cpi_client_accounts_add_feed_registry_page,
+    add_oracle::__cpi_client_accounts_add_oracle,
+    add_weighted_oracle::__cpi_client_accounts_add_weighted_oracle,
+    aggregate_feed::__cpi_client_accounts_aggregate_feed_prices,
+    claim_rewards::__cpi_client_accounts_claim_rewards,
+    close_merkle_root::__cpi_client_accounts_close_merkle_root,
+    initialize_feed_registry::__cpi_client_accounts_initialize_feed_registry,
+    initialize_operator_registry::__cpi_client_accounts_initialize_operator_registry,
+    initialize_oracle::__cpi_client_accounts_initialize_oracle,
+    initialize_price_accumulator::__cpi_client_accounts_initialize_price_accumulator,
+    initialize_price_history::__cpi_client_accounts_initialize_price_history,
+    initialize_signer_stats::__cpi_client_accounts_initialize_signer_stats,
+    migrate_oracle_state::__cpi_client_accounts_migrate_oracle_state,
+    migrate_price_feed::__cpi_client_accounts_migrate_price_feed,
+    override_rate_limit::__cpi_client_accounts_override_rate_limit,
+    refresh_basket_feed::__cpi_client_accounts_refresh_basket_feed,
+    refresh_derived_feed::__cpi_client_accounts_refresh_derived_feed,
+    rotate_oracle::__cpi_client_accounts_rotate_oracle,
+    set_aggregate_feed::__cpi_client_accounts_set_aggregate_feed,
+    set_basket_feed::__cpi_client_accounts_set_basket_feed,
+    set_circuit_breaker::__cpi_client_accounts_set_circuit_breaker,
+    set_derived_feed::__cpi_client_accounts_set_derived_feed,
+    set_fallback_price::__cpi_client_accounts_set_fallback_price,
+    set_feed_config::__cpi_client_accounts_set_feed_config,
+    set_feed_kind::__cpi_client_accounts_set_feed_kind,
+    set_feed_quorum::__cpi_client_accounts_set_feed_quorum,
+    set_feed_status::__cpi_client_accounts_set_feed_status,
+    set_feed_sunset::__cpi_client_accounts_set_feed_sunset,
+    set_operator::__cpi_client_accounts_set_operator,
+    set_operator_fee_share::__cpi_client_accounts_set_operator_fee_share,
+    set_oracle_weight::__cpi_client_accounts_set_oracle_weight,
+    set_pauser::__cpi_client_accounts_set_pauser,
+    set_price_ordering_tolerance::__cpi_client_accounts_set_price_ordering_tolerance,
+    set_push_policy::__cpi_client_accounts_set_push_policy,
+    set_quorum_mode::__cpi_client_accounts_set_quorum_mode,
+    set_rate_limit::__cpi_client_accounts_set_rate_limit,
+    set_registered_feeds_only::__cpi_client_accounts_set_registered_feeds_only,
+    set_secondary_oracle_state::__cpi_client_accounts_set_secondary_oracle_state,
+    submit_merkle_root::__cpi_client_accounts_submit_merkle_root,
+    update_admin::__cpi_client_accounts_update_admin,
+    update_data_feed::__cpi_client_accounts_update_data_feed,
+    update_price::__cpi_client_accounts_update_price,
+    update_price_with_proof::__cpi_client_accounts_update_price_with_proof,
+    update_prices::__cpi_client_accounts_update_prices,
+};
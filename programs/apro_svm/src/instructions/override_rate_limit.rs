@@ -0,0 +1,49 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RateLimitOverridden {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub override_until: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct OverrideRateLimit<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Lifts the feed's rate limit until `override_until`, e.g. to push
+/// corrections in quick succession. A past time ends the override.
+pub fn handler(
+    ctx: Context<OverrideRateLimit>,
+    feed_id: [u8; 32],
+    override_until: i64,
+) -> Result<()> {
+    ctx.accounts.feed_config.rate_limit.override_until = override_until;
+
+    emit!(RateLimitOverridden {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        override_until,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::{require_migrated, source_price};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct BasketFeedRefreshed {
+    pub oracle_state: Pubkey,
+    pub basket_id: [u8; 32],
+    pub value: u128,
+    pub oldest_time_stamp: u128,
+}
+
+#[derive(Accounts)]
+pub struct RefreshBasketFeed<'info> {
+    #[account(mut)]
+    pub basket_feed: Account<'info, BasketFeed>,
+}
+
+/// Recomputes a basket. Anyone may call it. `remaining_accounts` are each
+/// constituent's `PriceFeed` followed by its `FeedConfig`, in order; it
+/// fails if any of them has no `source_price` or is older than the basket's
+/// `max_staleness`.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshBasketFeed<'info>>) -> Result<()> {
+    let basket_feed = &mut ctx.accounts.basket_feed;
+    require!(
+        ctx.remaining_accounts.len() == 2 * basket_feed.constituents.len(),
+        ErrorCode::BasketConstituentMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let max_staleness = u128::from(basket_feed.max_staleness);
+    let mut prices = Vec::with_capacity(basket_feed.constituents.len());
+    for (constituent, accounts) in basket_feed
+        .constituents
+        .iter_mut()
+        .zip(ctx.remaining_accounts.chunks(2))
+    {
+        require_keys_eq!(
+            accounts[0].key(),
+            constituent.price_feed,
+            ErrorCode::BasketConstituentMismatch
+        );
+        require_keys_eq!(
+            accounts[1].key(),
+            constituent.feed_config,
+            ErrorCode::BasketConstituentMismatch
+        );
+        require_migrated::<PriceFeed>(&accounts[0])?;
+        let price_feed_loader = AccountLoader::<PriceFeed>::try_from(&accounts[0])?;
+        let price_feed = price_feed_loader.load()?;
+        let feed_config = FeedConfig::load(&accounts[1])?;
+        let source_price = source_price(&price_feed, feed_config.as_ref(), now)
+            .ok_or(ErrorCode::UnusableSourceFeed)?;
+        let decimals = source_price
+            .decimals
+            .ok_or(ErrorCode::MissingSourceDecimals)?;
+        require!(
+            u128::try_from(now)
+                .unwrap_or(0)
+                .saturating_sub(source_price.valid_time_stamp)
+                <= max_staleness,
+            ErrorCode::StaleBasketConstituent
+        );
+
+        constituent.valid_time_stamp = source_price.valid_time_stamp;
+        prices.push((source_price.benchmark_price, decimals));
+    }
+
+    basket_feed.value = basket_feed.compute(&prices)?;
+    basket_feed.updated_at = now;
+
+    emit!(BasketFeedRefreshed {
+        oracle_state: basket_feed.oracle_state,
+        basket_id: basket_feed.basket_id,
+        value: basket_feed.value,
+        oldest_time_stamp: basket_feed
+            .constituents
+            .iter()
+            .map(|constituent| constituent.valid_time_stamp)
+            .min()
+            .unwrap_or(0),
+    });
+
+    Ok(())
+}
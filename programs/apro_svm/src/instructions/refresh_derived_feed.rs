@@ -0,0 +1,94 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::{require_migrated, source_price};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DerivedFeedRefreshed {
+    pub oracle_state: Pubkey,
+    pub derived_id: [u8; 32],
+    pub price: u128,
+    pub oldest_time_stamp: u128,
+}
+
+#[derive(Accounts)]
+pub struct RefreshDerivedFeed<'info> {
+    #[account(mut)]
+    pub derived_feed: Account<'info, DerivedFeed>,
+    #[account(address = derived_feed.base_feed @ ErrorCode::DerivedInputMismatch)]
+    pub base_feed: AccountLoader<'info, PriceFeed>,
+    /// CHECK: The base feed's `FeedConfig` PDA, loaded with
+    /// `FeedConfig::load`; it must exist to give the feed's decimals.
+    #[account(address = derived_feed.base_feed_config @ ErrorCode::DerivedInputMismatch)]
+    pub base_feed_config: UncheckedAccount<'info>,
+    /// Required unless the formula is `Invert`.
+    pub quote_feed: Option<AccountLoader<'info, PriceFeed>>,
+    /// CHECK: The quote feed's `FeedConfig` PDA, checked against the derived
+    /// feed. Required unless the formula is `Invert`.
+    pub quote_feed_config: Option<UncheckedAccount<'info>>,
+}
+
+/// Recomputes a derived feed from its inputs. Anyone may call it; it fails
+/// if any input has no `source_price` or is older than the feed's
+/// `max_staleness`.
+pub fn handler(ctx: Context<RefreshDerivedFeed>) -> Result<()> {
+    let derived_feed = &mut ctx.accounts.derived_feed;
+    let mut inputs = vec![(
+        &ctx.accounts.base_feed,
+        ctx.accounts.base_feed_config.to_account_info(),
+    )];
+    match (
+        derived_feed.quote_feed,
+        ctx.accounts.quote_feed.as_ref(),
+        ctx.accounts.quote_feed_config.as_ref(),
+    ) {
+        (None, None, None) => {}
+        (Some(expected), Some(quote_feed), Some(quote_feed_config)) => {
+            require_keys_eq!(quote_feed.key(), expected, ErrorCode::DerivedInputMismatch);
+            require!(
+                derived_feed.quote_feed_config == Some(quote_feed_config.key()),
+                ErrorCode::DerivedInputMismatch
+            );
+            inputs.push((quote_feed, quote_feed_config.to_account_info()));
+        }
+        _ => return err!(ErrorCode::DerivedInputMismatch),
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    // (benchmark_price, decimals, valid_time_stamp) of each input
+    let mut prices = Vec::with_capacity(inputs.len());
+    for (price_feed, feed_config) in inputs {
+        require_migrated::<PriceFeed>(price_feed.as_ref())?;
+        let feed_config = FeedConfig::load(&feed_config)?;
+        let input = source_price(&*price_feed.load()?, feed_config.as_ref(), now)
+            .ok_or(ErrorCode::UnusableSourceFeed)?;
+        let decimals = input.decimals.ok_or(ErrorCode::MissingSourceDecimals)?;
+        require!(
+            u128::try_from(now)
+                .unwrap_or(0)
+                .saturating_sub(input.valid_time_stamp)
+                <= u128::from(derived_feed.max_staleness),
+            ErrorCode::StaleDerivedInput
+        );
+        prices.push((input.benchmark_price, decimals, input.valid_time_stamp));
+    }
+
+    let (quote_price, quote_decimals, _) = prices.get(1).copied().unwrap_or_default();
+    derived_feed.price =
+        derived_feed.compute(prices[0].0, prices[0].1, quote_price, quote_decimals)?;
+    derived_feed.oldest_time_stamp = prices
+        .iter()
+        .map(|(_, _, valid_time_stamp)| *valid_time_stamp)
+        .min()
+        .unwrap_or(0);
+    derived_feed.updated_at = now;
+
+    emit!(DerivedFeedRefreshed {
+        oracle_state: derived_feed.oracle_state,
+        derived_id: derived_feed.derived_id,
+        price: derived_feed.price,
+        oldest_time_stamp: derived_feed.oldest_time_stamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct OracleRotated {
+    pub old_oracle: [u8; 20],
+    pub new_oracle: [u8; 20],
+    pub weight: u64,
+}
+
+#[derive(Accounts)]
+pub struct RotateOracle<'info> {
+    #[account(
+        mut,
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    pub admin: Signer<'info>,
+}
+
+/// Replaces an oracle address, e.g. after a key rotation. Per-feed
+/// `allowed_oracles` lists and operator registrations naming the old address
+/// must be updated separately.
+pub fn handler(
+    ctx: Context<RotateOracle>,
+    old_oracle: [u8; 20],
+    new_oracle: [u8; 20],
+    weight: u64,
+) -> Result<()> {
+    ctx.accounts
+        .oracle_state
+        .load_mut()?
+        .rotate_oracle(old_oracle, new_oracle, weight)?;
+
+    emit!(OracleRotated {
+        old_oracle,
+        new_oracle,
+        weight,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct AggregateFeedConfigured {
+    pub aggregate_feed: Pubkey,
+    pub authority: Pubkey,
+    pub feed_id: [u8; 32],
+    pub oracle_states: Vec<Pubkey>,
+    pub max_staleness: u64,
+    pub min_sources: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetAggregateFeed<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AggregateFeed::INIT_SPACE,
+        seeds = [
+            b"aggregate_feed",
+            authority.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub aggregate_feed: Account<'info, AggregateFeed>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or reconfigures the authority's aggregate of `feed_id` over the
+/// price feeds of `oracle_states`. Any oracle state may be a source; the
+/// authority chooses which deployments it trusts.
+pub fn handler(
+    ctx: Context<SetAggregateFeed>,
+    feed_id: [u8; 32],
+    oracle_states: Vec<Pubkey>,
+    max_staleness: u64,
+    min_sources: u8,
+) -> Result<()> {
+    let addresses = |seed: &[u8]| {
+        oracle_states
+            .iter()
+            .map(|oracle_state| {
+                Pubkey::find_program_address(
+                    &[seed, oracle_state.as_ref(), feed_id.as_ref()],
+                    ctx.program_id,
+                )
+                .0
+            })
+            .collect()
+    };
+    ctx.accounts.aggregate_feed.configure(
+        ctx.accounts.authority.key(),
+        feed_id,
+        addresses(b"price_feed"),
+        addresses(b"feed_config"),
+        max_staleness,
+        min_sources,
+    )?;
+
+    emit!(AggregateFeedConfigured {
+        aggregate_feed: ctx.accounts.aggregate_feed.key(),
+        authority: ctx.accounts.authority.key(),
+        feed_id,
+        oracle_states,
+        max_staleness,
+        min_sources,
+    });
+
+    Ok(())
+}
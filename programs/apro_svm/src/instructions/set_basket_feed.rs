@@ -0,0 +1,84 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct BasketFeedConfigured {
+    pub oracle_state: Pubkey,
+    pub basket_id: [u8; 32],
+    pub constituents: Vec<BasketWeight>,
+    pub decimals: u8,
+    pub max_staleness: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(basket_id: [u8; 32])]
+pub struct SetBasketFeed<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + BasketFeed::INIT_SPACE,
+        seeds = [
+            b"basket_feed",
+            oracle_state.key().as_ref(),
+            basket_id.as_ref()
+        ],
+        bump
+    )]
+    pub basket_feed: Account<'info, BasketFeed>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Defines a basket over price feeds of the oracle state. Constituents'
+/// decimals are read from their `FeedConfig`s on each refresh. Redefining a
+/// basket clears its value until the next refresh.
+pub fn handler(
+    ctx: Context<SetBasketFeed>,
+    basket_id: [u8; 32],
+    constituents: Vec<BasketWeight>,
+    decimals: u8,
+    max_staleness: u64,
+) -> Result<()> {
+    let oracle_state = ctx.accounts.oracle_state.key();
+    let address = |seed: &[u8], feed_id: &[u8; 32]| {
+        Pubkey::find_program_address(
+            &[seed, oracle_state.as_ref(), feed_id.as_ref()],
+            ctx.program_id,
+        )
+        .0
+    };
+    let basket_constituents = constituents
+        .iter()
+        .map(|constituent| BasketConstituent {
+            price_feed: address(b"price_feed", &constituent.feed_id),
+            feed_config: address(b"feed_config", &constituent.feed_id),
+            weight: constituent.weight,
+            valid_time_stamp: 0,
+        })
+        .collect();
+    ctx.accounts.basket_feed.configure(
+        oracle_state,
+        basket_id,
+        basket_constituents,
+        decimals,
+        max_staleness,
+    )?;
+
+    emit!(BasketFeedConfigured {
+        oracle_state,
+        basket_id,
+        constituents,
+        decimals,
+        max_staleness,
+    });
+
+    Ok(())
+}
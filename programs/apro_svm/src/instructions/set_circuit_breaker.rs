@@ -0,0 +1,72 @@
+use crate::constants::MAX_ORACLES;
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::require_migrated;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CircuitBreakerConfigured {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub circuit_breaker: CircuitBreaker,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetCircuitBreaker<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"price_feed",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_feed: AccountLoader<'info, PriceFeed>,
+    pub admin: Signer<'info>,
+}
+
+/// Sets the deviation limits of an existing feed. With
+/// `RequireConfirmation`, `confirmation_signatures` must exceed the oracle
+/// state's quorum: its `required_signatures`, or its `weight_threshold`
+/// (and be within the oracles' total weight) in weight mode.
+pub fn handler(
+    ctx: Context<SetCircuitBreaker>,
+    feed_id: [u8; 32],
+    circuit_breaker: CircuitBreaker,
+) -> Result<()> {
+    let oracle_state = ctx.accounts.oracle_state.load()?;
+    let (quorum, max_confirmation) = match oracle_state.quorum_mode() {
+        QuorumMode::Count => (oracle_state.required_signatures, MAX_ORACLES as u64),
+        QuorumMode::Weight => (
+            oracle_state.weight_threshold,
+            oracle_state.weight_of_all(oracle_state.oracles()),
+        ),
+    };
+    require!(
+        circuit_breaker.action != BreakerAction::RequireConfirmation
+            || (circuit_breaker.confirmation_signatures > quorum
+                && circuit_breaker.confirmation_signatures <= max_confirmation),
+        ErrorCode::InvalidCircuitBreaker
+    );
+
+    require_migrated::<PriceFeed>(ctx.accounts.price_feed.as_ref())?;
+    ctx.accounts
+        .price_feed
+        .load_mut()?
+        .set_circuit_breaker(circuit_breaker);
+
+    emit!(CircuitBreakerConfigured {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        circuit_breaker,
+    });
+
+    Ok(())
+}
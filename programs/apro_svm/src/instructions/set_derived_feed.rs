@@ -0,0 +1,87 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DerivedFeedConfigured {
+    pub oracle_state: Pubkey,
+    pub derived_id: [u8; 32],
+    pub formula: DerivedFormula,
+    pub base_feed_id: [u8; 32],
+    pub quote_feed_id: Option<[u8; 32]>,
+    pub decimals: u8,
+    pub max_staleness: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(derived_id: [u8; 32])]
+pub struct SetDerivedFeed<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DerivedFeed::INIT_SPACE,
+        seeds = [
+            b"derived_feed",
+            oracle_state.key().as_ref(),
+            derived_id.as_ref()
+        ],
+        bump
+    )]
+    pub derived_feed: Account<'info, DerivedFeed>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Defines a derived feed over the price feeds `base_feed_id` and, except
+/// for `Invert`, `quote_feed_id` of the oracle state. The inputs' decimals
+/// are read from their `FeedConfig`s on each refresh; `decimals` are those
+/// wanted for the result.
+pub fn handler(
+    ctx: Context<SetDerivedFeed>,
+    derived_id: [u8; 32],
+    formula: DerivedFormula,
+    base_feed_id: [u8; 32],
+    quote_feed_id: Option<[u8; 32]>,
+    decimals: u8,
+    max_staleness: u64,
+) -> Result<()> {
+    let oracle_state = ctx.accounts.oracle_state.key();
+    let address = |seed: &[u8], feed_id: [u8; 32]| {
+        Pubkey::find_program_address(
+            &[seed, oracle_state.as_ref(), feed_id.as_ref()],
+            ctx.program_id,
+        )
+        .0
+    };
+
+    ctx.accounts.derived_feed.configure(
+        oracle_state,
+        derived_id,
+        formula,
+        address(b"price_feed", base_feed_id),
+        address(b"feed_config", base_feed_id),
+        quote_feed_id.map(|feed_id| address(b"price_feed", feed_id)),
+        quote_feed_id.map(|feed_id| address(b"feed_config", feed_id)),
+        decimals,
+        max_staleness,
+    )?;
+
+    emit!(DerivedFeedConfigured {
+        oracle_state,
+        derived_id,
+        formula,
+        base_feed_id,
+        quote_feed_id,
+        decimals,
+        max_staleness,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::constants::MAX_FALLBACK_DURATION;
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FallbackPriceSet {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub price: u128,
+    pub expire_at: i64,
+    pub authority: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetFallbackPrice<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.is_admin_or_pauser(&authority.key()) @ ErrorCode::UnauthorizedPauser
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"price_feed",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_feed: AccountLoader<'info, PriceFeed>,
+    /// CHECK: the feed's `FeedConfig` PDA, read only if it exists.
+    #[account(
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: UncheckedAccount<'info>,
+    /// The admin or the pauser.
+    pub authority: Signer<'info>,
+}
+
+/// Publishes a manual price for an existing feed during a DON outage,
+/// flagged as `PriceSource::Manual` and valid for `duration` seconds, at
+/// most `MAX_FALLBACK_DURATION`. The next signed report timestamped after
+/// the fallback was set replaces it; older reports are rejected as stale.
+/// Retired and sunset feeds cannot be revived this way.
+pub fn handler(
+    ctx: Context<SetFallbackPrice>,
+    feed_id: [u8; 32],
+    price: u128,
+    duration: i64,
+) -> Result<()> {
+    require!(
+        duration > 0 && duration <= MAX_FALLBACK_DURATION,
+        ErrorCode::InvalidFallbackDuration
+    );
+    require!(price > 0, ErrorCode::InvalidFallbackPrice);
+
+    require_migrated::<PriceFeed>(ctx.accounts.price_feed.as_ref())?;
+    let price_feed = &mut ctx.accounts.price_feed.load_mut()?;
+    require!(
+        price_feed.feed_id == feed_id,
+        ErrorCode::InvalidFallbackPrice
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(config) = FeedConfig::load(&ctx.accounts.feed_config)? {
+        config.require_live(now)?;
+    }
+    let expire_at = now + duration;
+    price_feed.set_fallback_price(price, now, expire_at, ctx.accounts.authority.key())?;
+
+    emit!(FallbackPriceSet {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        price,
+        expire_at,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FeedConfigUpdated {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub decimals: u8,
+    pub base_symbol: String,
+    pub quote_symbol: String,
+    pub description: String,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetFeedConfig<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeedConfig::INIT_SPACE,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetFeedConfig>,
+    feed_id: [u8; 32],
+    decimals: u8,
+    base_symbol: String,
+    quote_symbol: String,
+    description: String,
+) -> Result<()> {
+    let oracle_state = ctx.accounts.oracle_state.key();
+
+    ctx.accounts.feed_config.update_metadata(
+        oracle_state,
+        feed_id,
+        decimals,
+        base_symbol.clone(),
+        quote_symbol.clone(),
+        description.clone(),
+    )?;
+
+    emit!(FeedConfigUpdated {
+        oracle_state,
+        feed_id,
+        decimals,
+        base_symbol,
+        quote_symbol,
+        description,
+    });
+
+    Ok(())
+}
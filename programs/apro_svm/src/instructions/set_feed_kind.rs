@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FeedKindUpdated {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub kind: FeedKind,
+    pub field_names: Vec<String>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetFeedKind<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetFeedKind>,
+    feed_id: [u8; 32],
+    kind: FeedKind,
+    field_names: Vec<String>,
+) -> Result<()> {
+    ctx.accounts
+        .feed_config
+        .set_kind(kind, field_names.clone())?;
+
+    emit!(FeedKindUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        kind,
+        field_names,
+    });
+
+    Ok(())
+}
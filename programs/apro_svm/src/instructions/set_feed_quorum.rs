@@ -0,0 +1,61 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FeedQuorumUpdated {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub required_signatures: u64,
+    pub weight_threshold: u64,
+    pub allowed_oracles: Vec<[u8; 20]>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetFeedQuorum<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Overrides the signature and weight thresholds of a feed and optionally
+/// restricts the oracles counted for it. Zero thresholds and an empty list
+/// restore the oracle state's defaults.
+pub fn handler(
+    ctx: Context<SetFeedQuorum>,
+    feed_id: [u8; 32],
+    required_signatures: u64,
+    weight_threshold: u64,
+    allowed_oracles: Vec<[u8; 20]>,
+) -> Result<()> {
+    ctx.accounts.feed_config.set_quorum(
+        &*ctx.accounts.oracle_state.load()?,
+        required_signatures,
+        weight_threshold,
+        allowed_oracles.clone(),
+    )?;
+
+    emit!(FeedQuorumUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        required_signatures,
+        weight_threshold,
+        allowed_oracles,
+    });
+
+    Ok(())
+}
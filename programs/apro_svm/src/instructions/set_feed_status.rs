@@ -0,0 +1,68 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FeedStatusUpdated {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub old_status: FeedStatus,
+    pub new_status: FeedStatus,
+    pub sunset_at: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetFeedStatus<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    /// The registry page holding the feed, if it is registered.
+    #[account(
+        mut,
+        seeds = [
+            b"feed_registry_page",
+            oracle_state.key().as_ref(),
+            feed_registry_page.page_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub feed_registry_page: Option<Account<'info, FeedRegistryPage>>,
+    pub admin: Signer<'info>,
+}
+
+/// Moves the feed to `status`. Reactivating a feed clears its sunset date;
+/// a retired feed, including one past its sunset, stays retired. The feed's
+/// registry entry, if any, follows.
+pub fn handler(ctx: Context<SetFeedStatus>, feed_id: [u8; 32], status: FeedStatus) -> Result<()> {
+    let feed_config = &mut ctx.accounts.feed_config;
+    let now = Clock::get()?.unix_timestamp;
+    let old_status = feed_config.effective_status(now);
+
+    feed_config.set_status(status, now)?;
+    if let Some(page) = ctx.accounts.feed_registry_page.as_mut() {
+        page.set_status(feed_id, status)?;
+    }
+
+    emit!(FeedStatusUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        old_status,
+        new_status: status,
+        sunset_at: feed_config.sunset_at,
+    });
+
+    Ok(())
+}
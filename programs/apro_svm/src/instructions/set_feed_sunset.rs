@@ -0,0 +1,59 @@
+use crate::errors::ErrorCode;
+use crate::instructions::set_feed_status::FeedStatusUpdated;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetFeedSunset<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    /// The registry page holding the feed, if it is registered.
+    #[account(
+        mut,
+        seeds = [
+            b"feed_registry_page",
+            oracle_state.key().as_ref(),
+            feed_registry_page.page_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub feed_registry_page: Option<Account<'info, FeedRegistryPage>>,
+    pub admin: Signer<'info>,
+}
+
+/// Deprecates the feed and announces when it retires. Updates are accepted
+/// until `sunset_at` and refused from then on.
+pub fn handler(ctx: Context<SetFeedSunset>, feed_id: [u8; 32], sunset_at: i64) -> Result<()> {
+    let feed_config = &mut ctx.accounts.feed_config;
+    let now = Clock::get()?.unix_timestamp;
+    let old_status = feed_config.effective_status(now);
+
+    feed_config.schedule_sunset(sunset_at, now)?;
+    if let Some(page) = ctx.accounts.feed_registry_page.as_mut() {
+        page.set_status(feed_id, feed_config.status)?;
+    }
+
+    emit!(FeedStatusUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        old_status,
+        new_status: feed_config.status,
+        sunset_at,
+    });
+
+    Ok(())
+}
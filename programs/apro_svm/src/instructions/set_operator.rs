@@ -0,0 +1,42 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct OperatorSet {
+    pub oracle_state: Pubkey,
+    pub oracle: [u8; 20],
+    pub payout: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle: [u8; 20])]
+pub struct SetOperator<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin,
+        constraint = oracle_state.load()?.oracles().contains(&oracle) @ ErrorCode::OracleNotFound
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"operator_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub operator_registry: Account<'info, OperatorRegistry>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetOperator>, oracle: [u8; 20], payout: Pubkey) -> Result<()> {
+    ctx.accounts
+        .operator_registry
+        .set_operator(oracle, payout)?;
+
+    emit!(OperatorSet {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        oracle,
+        payout,
+    });
+
+    Ok(())
+}
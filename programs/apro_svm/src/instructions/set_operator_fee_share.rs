@@ -0,0 +1,41 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct OperatorFeeShareUpdated {
+    pub oracle_state: Pubkey,
+    pub old_operator_fee_bps: u64,
+    pub new_operator_fee_bps: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetOperatorFeeShare<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"operator_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub operator_registry: Account<'info, OperatorRegistry>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetOperatorFeeShare>, operator_fee_bps: u64) -> Result<()> {
+    let operator_registry = &mut ctx.accounts.operator_registry;
+    let old_operator_fee_bps = operator_registry.operator_fee_bps;
+
+    operator_registry.set_operator_fee_bps(operator_fee_bps)?;
+
+    emit!(OperatorFeeShareUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        old_operator_fee_bps,
+        new_operator_fee_bps: operator_fee_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct OracleWeightUpdated {
+    pub oracle: [u8; 20],
+    pub old_weight: u64,
+    pub new_weight: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleWeight<'info> {
+    #[account(
+        mut,
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetOracleWeight>, oracle: [u8; 20], weight: u64) -> Result<()> {
+    let oracle_state = &mut ctx.accounts.oracle_state.load_mut()?;
+    let old_weight = oracle_state.weight_of(&oracle);
+    oracle_state.set_oracle_weight(oracle, weight)?;
+
+    emit!(OracleWeightUpdated {
+        oracle,
+        old_weight,
+        new_weight: weight,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PauserUpdated {
+    pub oracle_state: Pubkey,
+    pub pauser: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct SetPauser<'info> {
+    #[account(
+        mut,
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    pub admin: Signer<'info>,
+}
+
+/// Sets the key that may act in emergencies alongside the admin, currently
+/// by setting fallback prices. `Pubkey::default()` removes the role.
+pub fn handler(ctx: Context<SetPauser>, pauser: Pubkey) -> Result<()> {
+    ctx.accounts.oracle_state.load_mut()?.pauser = pauser;
+
+    emit!(PauserUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        pauser,
+    });
+
+    Ok(())
+}
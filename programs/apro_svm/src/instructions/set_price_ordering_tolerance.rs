@@ -0,0 +1,41 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PriceOrderingToleranceUpdated {
+    pub oracle_state: Pubkey,
+    pub old_tolerance_bps: u64,
+    pub new_tolerance_bps: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetPriceOrderingTolerance<'info> {
+    #[account(
+        mut,
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetPriceOrderingTolerance>, tolerance_bps: u64) -> Result<()> {
+    require!(
+        tolerance_bps <= BPS_DENOMINATOR,
+        ErrorCode::InvalidOrderingTolerance
+    );
+
+    let oracle_state = &mut ctx.accounts.oracle_state.load_mut()?;
+    let old_tolerance_bps = oracle_state.price_ordering_tolerance_bps;
+    oracle_state.price_ordering_tolerance_bps = tolerance_bps;
+
+    emit!(PriceOrderingToleranceUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        old_tolerance_bps,
+        new_tolerance_bps: tolerance_bps,
+    });
+
+    Ok(())
+}
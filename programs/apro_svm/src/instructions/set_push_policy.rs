@@ -0,0 +1,49 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PushPolicyUpdated {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub push_policy: PushPolicy,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetPushPolicy<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Publishes the feed's heartbeat and deviation threshold. Updates record
+/// which of them triggered each report.
+pub fn handler(
+    ctx: Context<SetPushPolicy>,
+    feed_id: [u8; 32],
+    push_policy: PushPolicy,
+) -> Result<()> {
+    ctx.accounts.feed_config.set_push_policy(push_policy)?;
+
+    emit!(PushPolicyUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        push_policy,
+    });
+
+    Ok(())
+}
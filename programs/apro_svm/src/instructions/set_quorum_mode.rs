@@ -0,0 +1,38 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct QuorumModeUpdated {
+    pub oracle_state: Pubkey,
+    pub quorum_mode: QuorumMode,
+    pub weight_threshold: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetQuorumMode<'info> {
+    #[account(
+        mut,
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetQuorumMode>,
+    quorum_mode: QuorumMode,
+    weight_threshold: u64,
+) -> Result<()> {
+    let oracle_state = &mut ctx.accounts.oracle_state.load_mut()?;
+    oracle_state.set_quorum_mode(quorum_mode, weight_threshold)?;
+
+    emit!(QuorumModeUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        quorum_mode,
+        weight_threshold,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RateLimitUpdated {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub rate_limit: RateLimit,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetRateLimit<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Sets the minimum interval between accepted updates of the feed. An
+/// active override is kept.
+pub fn handler(
+    ctx: Context<SetRateLimit>,
+    feed_id: [u8; 32],
+    min_interval_slots: u64,
+    min_interval_seconds: u64,
+) -> Result<()> {
+    let feed_config = &mut ctx.accounts.feed_config;
+    feed_config.rate_limit.min_interval_slots = min_interval_slots;
+    feed_config.rate_limit.min_interval_seconds = min_interval_seconds;
+
+    emit!(RateLimitUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        rate_limit: feed_config.rate_limit,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RegisteredFeedsOnlyUpdated {
+    pub oracle_state: Pubkey,
+    pub enabled: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetRegisteredFeedsOnly<'info> {
+    #[account(
+        mut,
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    pub admin: Signer<'info>,
+}
+
+/// Toggles whether new feeds need a `FeedConfig` (see `set_feed_config`)
+/// before `update_price` creates them. Existing feeds are unaffected.
+pub fn handler(ctx: Context<SetRegisteredFeedsOnly>, enabled: bool) -> Result<()> {
+    ctx.accounts.oracle_state.load_mut()?.registered_feeds_only = enabled.into();
+
+    emit!(RegisteredFeedsOnlyUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        enabled,
+    });
+
+    Ok(())
+}
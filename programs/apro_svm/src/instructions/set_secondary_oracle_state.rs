@@ -0,0 +1,54 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SecondaryOracleStateUpdated {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub secondary_oracle_state: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetSecondaryOracleState<'info> {
+    #[account(
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    /// The oracle state whose quorum the feed additionally requires, or
+    /// `None` to return the feed to single attestation.
+    #[account(constraint = secondary_oracle_state.is_migrated() @ ErrorCode::AccountNotMigrated)]
+    pub secondary_oracle_state: Option<AccountLoader<'info, OracleState>>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetSecondaryOracleState>, feed_id: [u8; 32]) -> Result<()> {
+    let secondary_oracle_state = ctx
+        .accounts
+        .secondary_oracle_state
+        .as_ref()
+        .map(|secondary| secondary.key());
+    ctx.accounts
+        .feed_config
+        .set_secondary_oracle_state(secondary_oracle_state)?;
+
+    emit!(SecondaryOracleStateUpdated {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        feed_id,
+        secondary_oracle_state,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::constants::MERKLE_ROOT_TTL;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct MerkleRootSubmitted {
+    pub oracle_state: Pubkey,
+    pub root: [u8; 32],
+    pub epoch_and_round: u128,
+    pub signers: Vec<[u8; 20]>,
+    pub expires_at: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32])]
+pub struct SubmitMerkleRoot<'info> {
+    #[account(
+        seeds = [b"oracle_state", oracle_state.load_migrated()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MerkleRoot::INIT_SPACE,
+        seeds = [
+            b"merkle_root",
+            oracle_state.key().as_ref(),
+            root.as_ref()
+        ],
+        bump
+    )]
+    pub merkle_root: Account<'info, MerkleRoot>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Caches a root signed by the oracle state's quorum. Feeds with a stricter
+/// quorum are checked against the root's signers when a report is applied.
+pub fn handler(
+    ctx: Context<SubmitMerkleRoot>,
+    root: [u8; 32],
+    config_digest: [u8; 32],
+    epoch_and_round: u128,
+    extra_hash: [u8; 32],
+    signatures: Vec<[u8; 64]>,
+    recovery_ids: Vec<u8>,
+) -> Result<()> {
+    let h = signing_hash(&root, &config_digest, epoch_and_round, &extra_hash);
+    let oracle_state = &ctx.accounts.oracle_state.load()?;
+    let signers = verify_quorum(
+        oracle_state,
+        &Quorum::new(oracle_state, None),
+        &h,
+        &signatures,
+        &recovery_ids,
+    )?;
+
+    let expires_at = Clock::get()?.unix_timestamp + MERKLE_ROOT_TTL;
+
+    ctx.accounts.merkle_root.initialize(
+        ctx.accounts.oracle_state.key(),
+        root,
+        config_digest,
+        epoch_and_round,
+        extra_hash,
+        signers.clone(),
+        expires_at,
+        ctx.accounts.payer.key(),
+    )?;
+
+    emit!(MerkleRootSubmitted {
+        oracle_state: ctx.accounts.oracle_state.key(),
+        root,
+        epoch_and_round,
+        signers,
+        expires_at,
+    });
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::errors::ErrorCode;
 use crate::states::*;
+use crate::utils::*;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -12,15 +13,15 @@ pub struct AdminUpdated {
 pub struct UpdateAdmin<'info> {
     #[account(
         mut,
-        has_one = admin @ ErrorCode::UnauthorizedAdmin
+        constraint = oracle_state.load_migrated()?.admin == admin.key() @ ErrorCode::UnauthorizedAdmin
     )]
-    pub oracle_state: Account<'info, OracleState>,
+    pub oracle_state: AccountLoader<'info, OracleState>,
 
     pub admin: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<UpdateAdmin>, new_admin: Pubkey) -> Result<()> {
-    let oracle_state = &mut ctx.accounts.oracle_state;
+    let oracle_state = &mut ctx.accounts.oracle_state.load_mut()?;
     let old_admin = oracle_state.admin;
 
     oracle_state.update_admin(new_admin)?;
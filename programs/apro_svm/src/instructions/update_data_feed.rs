@@ -0,0 +1,125 @@
+use crate::constants::UPDATE_FEE_LAMPORTS;
+use crate::errors::ErrorCode;
+use crate::instructions::update_price::record_signers;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DataFeedUpdated {
+    pub feed_id: [u8; 32],
+    pub valid_time_stamp: u128,
+    pub observe_time_stamp: u128,
+    pub expire_at: u128,
+    pub values: Vec<i128>,
+    pub epoch_and_round: u128,
+}
+
+#[derive(Accounts)]
+#[instruction(report: DataReport)]
+pub struct UpdateDataFeed<'info> {
+    #[account(
+        seeds = [b"oracle_state", oracle_state.load_migrated()?.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = admin,
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            report.feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Account<'info, FeedConfig>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DataFeed::INIT_SPACE,
+        seeds = [
+            b"data_feed",
+            oracle_state.key().as_ref(),
+            report.feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub data_feed: Account<'info, DataFeed>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub admin: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"operator_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub operator_registry: Option<Account<'info, OperatorRegistry>>,
+    /// Records which oracles signed the report. Required once the
+    /// `OracleState`'s signer stats are initialized.
+    #[account(
+        mut,
+        seeds = [b"signer_stats", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub signer_stats: Option<Account<'info, SignerStats>>,
+}
+
+/// Stores a signed report of a `Signed` or `Data` feed. The feed must have a
+/// `FeedConfig` declaring its kind and fields.
+pub fn handler(ctx: Context<UpdateDataFeed>, report: DataReport) -> Result<()> {
+    require!(
+        report.values.len() == ctx.accounts.feed_config.value_count()?,
+        ErrorCode::FieldCountMismatch
+    );
+    ctx.accounts.feed_config.require_single_attestation()?;
+    ctx.accounts
+        .feed_config
+        .require_live(Clock::get()?.unix_timestamp)?;
+
+    // A replayed older report must not roll the feed back.
+    require!(
+        report.valid_time_stamp > ctx.accounts.data_feed.valid_time_stamp,
+        ErrorCode::StaleReport
+    );
+
+    let oracle_state = &ctx.accounts.oracle_state.load()?;
+    let verified_oracles = report.verify(
+        oracle_state,
+        &Quorum::new(oracle_state, Some(&ctx.accounts.feed_config)),
+    )?;
+
+    record_signers(
+        ctx.accounts.signer_stats.as_mut(),
+        oracle_state,
+        &verified_oracles,
+    )?;
+    let operator_amount = accrue_operator_rewards(
+        ctx.accounts.operator_registry.as_mut(),
+        report.feed_id,
+        &verified_oracles,
+    )?;
+    transfer_fees(
+        &ctx.accounts.payer,
+        &ctx.accounts.admin,
+        ctx.accounts.operator_registry.as_ref(),
+        &ctx.accounts.system_program,
+        UPDATE_FEE_LAMPORTS,
+        operator_amount,
+    )?;
+
+    let data_feed = &mut ctx.accounts.data_feed;
+    report.store(data_feed)?;
+
+    emit!(DataFeedUpdated {
+        feed_id: report.feed_id,
+        valid_time_stamp: report.valid_time_stamp,
+        observe_time_stamp: report.observe_time_stamp,
+        expire_at: report.expire_at,
+        values: data_feed.values.clone(),
+        epoch_and_round: report.epoch_and_round,
+    });
+
+    Ok(())
+}
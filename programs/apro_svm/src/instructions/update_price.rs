@@ -1,11 +1,9 @@
+use crate::constants::UPDATE_FEE_LAMPORTS;
 use crate::errors::ErrorCode;
 use crate::states::*;
+use crate::utils::*;
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{
-    keccak::hash as keccak_hash, msg, secp256k1_recover::secp256k1_recover,
-};
-use anchor_lang::system_program;
-use ethabi::{encode, Token};
+use std::cell::RefMut;
 
 #[event]
 pub struct PriceUpdated {
@@ -23,19 +21,59 @@ pub struct PriceUpdated {
     pub extra_hash: [u8; 32],
 }
 
+impl From<&PriceReport> for PriceUpdated {
+    fn from(report: &PriceReport) -> Self {
+        Self {
+            feed_id: report.feed_id,
+            valid_time_stamp: report.valid_time_stamp,
+            observe_time_stamp: report.observe_time_stamp,
+            native_fee: report.native_fee,
+            apro_token_fee: report.apro_token_fee,
+            expire_at: report.expire_at,
+            benchmark_price: report.benchmark_price,
+            ask_price: report.ask_price,
+            bid_price: report.bid_price,
+            config_digest: report.config_digest,
+            epoch_and_round: report.epoch_and_round,
+            extra_hash: report.extra_hash,
+        }
+    }
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub feed_id: [u8; 32],
+    pub previous_price: u128,
+    pub reported_price: u128,
+    pub deviation_bps: u64,
+    pub action: BreakerAction,
+    pub state: BreakerState,
+}
+
+#[event]
+pub struct CircuitBreakerReset {
+    pub feed_id: [u8; 32],
+    pub previous_state: BreakerState,
+}
+
+#[event]
+pub struct FallbackPriceCleared {
+    pub feed_id: [u8; 32],
+}
+
 #[derive(Accounts)]
 #[instruction(feed_id: [u8; 32])]
 pub struct UpdatePrice<'info> {
     #[account(
-        seeds = [b"oracle_state", oracle_state.id.to_le_bytes().as_ref()],
+        seeds = [b"oracle_state", oracle_state.load_migrated()?.id.to_le_bytes().as_ref()],
         bump,
         has_one = admin,
     )]
-    pub oracle_state: Account<'info, OracleState>,
+    pub oracle_state: AccountLoader<'info, OracleState>,
     #[account(
         init_if_needed,
         payer = payer,
-        space = 8 + 32 + 16 + 16 + 16 + 16 + 16 + 16 + 16 + 16 + 32 + 16 + 32,
+        space = PriceFeed::LEN,
         seeds = [
             b"price_feed",
             oracle_state.key().as_ref(),
@@ -43,12 +81,85 @@ pub struct UpdatePrice<'info> {
         ],
         bump
     )]
-    pub price_feed: Account<'info, PriceFeed>,
+    pub price_feed: AccountLoader<'info, PriceFeed>,
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(mut)]
     pub admin: SystemAccount<'info>,
     pub system_program: Program<'info, System>,
+    /// When present, the operator share of the update fee is credited to the
+    /// registered operators whose signatures verified.
+    #[account(
+        mut,
+        seeds = [b"operator_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub operator_registry: Option<Account<'info, OperatorRegistry>>,
+    /// When present, the report is appended to the feed's price history.
+    #[account(
+        mut,
+        seeds = [
+            b"price_history",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_history: Option<AccountLoader<'info, PriceHistory>>,
+    /// When present, the feed's TWAP and EMA accumulators are advanced.
+    #[account(
+        mut,
+        seeds = [
+            b"price_accumulator",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_accumulator: Option<AccountLoader<'info, PriceAccumulator>>,
+    /// Required when a feed is created under an `OracleState` with a feed
+    /// registry; the new feed is recorded in the registry's current page.
+    #[account(
+        mut,
+        seeds = [b"feed_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_registry_page",
+            oracle_state.key().as_ref(),
+            feed_registry_page.page_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub feed_registry_page: Option<Account<'info, FeedRegistryPage>>,
+    /// CHECK: The feed's `FeedConfig` PDA, which may not exist yet. Loaded
+    /// with `FeedConfig::load`, which checks its owner; its quorum override
+    /// applies and, under `registered_feeds_only`, it must exist to create the
+    /// feed. Optional so clients written before feed configs keep working.
+    #[account(
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: Option<UncheckedAccount<'info>>,
+    /// Required when the feed's config names a secondary oracle state, whose
+    /// quorum must also sign the report.
+    #[account(constraint = secondary_oracle_state.is_migrated() @ ErrorCode::AccountNotMigrated)]
+    pub secondary_oracle_state: Option<AccountLoader<'info, OracleState>>,
+    /// Records which oracles signed the report. Required once the
+    /// `OracleState`'s signer stats are initialized.
+    #[account(
+        mut,
+        seeds = [b"signer_stats", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub signer_stats: Option<Account<'info, SignerStats>>,
 }
 
 pub fn handler(
@@ -68,86 +179,7 @@ pub fn handler(
     signatures: Vec<[u8; 64]>,
     recovery_ids: Vec<u8>,
 ) -> Result<()> {
-    let oracle_state = &ctx.accounts.oracle_state;
-    let price_feed = &mut ctx.accounts.price_feed;
-    let fee_amount = 1000000;
-
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.payer.to_account_info(),
-                to: ctx.accounts.admin.to_account_info(),
-            },
-        ),
-        fee_amount,
-    )?;
-
-    require!(
-        signatures.len() == recovery_ids.len(),
-        ErrorCode::ArrayLengthMismatch
-    );
-
-    require!(
-        signatures.len() as u64 >= oracle_state.required_signatures,
-        ErrorCode::InsufficientSignatures
-    );
-
-    let report_data = vec![
-        Token::FixedBytes(feed_id.to_vec()),
-        Token::Uint(valid_time_stamp.into()),
-        Token::Uint(observe_time_stamp.into()),
-        Token::Uint(native_fee.into()),
-        Token::Uint(apro_token_fee.into()),
-        Token::Uint(expire_at.into()),
-        Token::Uint(benchmark_price.into()),
-        Token::Uint(ask_price.into()),
-        Token::Uint(bid_price.into()),
-    ];
-
-    let report_context = vec![
-        Token::FixedBytes(config_digest.to_vec()),
-        Token::Uint(epoch_and_round.into()),
-        Token::FixedBytes(extra_hash.to_vec()),
-    ];
-
-    let encoded_report_data = encode(&report_data);
-    let report_data_hash = keccak_hash(&encoded_report_data);
-    msg!("Report Data Hash: 0x{}", hex::encode(report_data_hash));
-
-    let encoded_report_context = encode(&report_context);
-    let value = [report_data_hash.as_ref(), &encoded_report_context].concat();
-    msg!("Concatenated Value: 0x{}", hex::encode(&value));
-
-    let h = keccak_hash(&value);
-    msg!("Final Hash (h): 0x{}", hex::encode(h));
-
-    let mut verified_signatures = 0;
-    for i in 0..signatures.len() {
-        let recovered_pubkey = secp256k1_recover(&h.to_bytes(), recovery_ids[i], &signatures[i])
-            .map_err(|_| ErrorCode::SignatureVerificationFailed)?;
-
-        let recovered_pubkey_bytes = recovered_pubkey.to_bytes();
-
-        let pubkey_hash = keccak_hash(&recovered_pubkey_bytes);
-        let eth_address = &pubkey_hash.to_bytes()[12..];
-
-        if oracle_state
-            .oracles
-            .contains(&eth_address.try_into().unwrap())
-        {
-            verified_signatures += 1;
-        }
-
-        msg!("Recovered Ethereum address: 0x{}", hex::encode(eth_address));
-    }
-
-    require!(
-        verified_signatures >= oracle_state.required_signatures,
-        ErrorCode::InsufficientValidSignatures
-    );
-
-    price_feed.update_price(
+    let report = PriceReport {
         feed_id,
         valid_time_stamp,
         observe_time_stamp,
@@ -160,22 +192,268 @@ pub fn handler(
         config_digest,
         epoch_and_round,
         extra_hash,
+        signatures,
+        recovery_ids,
+    };
+
+    let feed_config = match &ctx.accounts.feed_config {
+        Some(feed_config) => FeedConfig::load(feed_config)?,
+        None => None,
+    };
+    let oracle_state = &ctx.accounts.oracle_state.load()?;
+    let quorum = Quorum::new(oracle_state, feed_config.as_ref());
+    let verified_oracles = match feed_config
+        .as_ref()
+        .and_then(|config| config.secondary_oracle_state)
+    {
+        Some(secondary_key) => {
+            let secondary = ctx
+                .accounts
+                .secondary_oracle_state
+                .as_ref()
+                .filter(|secondary| secondary.key() == secondary_key)
+                .ok_or(ErrorCode::SecondaryOracleStateRequired)?;
+            let secondary = &secondary.load()?;
+            report.verify_dual(
+                (oracle_state, &quorum),
+                (secondary, &Quorum::new(secondary, None)),
+            )?
+        }
+        None => report.verify(oracle_state, &quorum)?,
+    };
+
+    let price_feed = &mut load_price_feed(&ctx.accounts.price_feed)?;
+    record_new_feed(
+        oracle_state,
+        price_feed,
+        feed_config.as_ref(),
+        ctx.accounts.feed_registry.as_mut(),
+        ctx.accounts.feed_registry_page.as_mut(),
+        feed_id,
     )?;
 
-    emit!(PriceUpdated {
+    let applied = apply_report(
+        &report,
+        &verified_oracles,
+        ctx.accounts.payer.key(),
+        oracle_state,
+        feed_config.as_ref(),
+        price_feed,
+        ctx.accounts.price_history.as_ref(),
+        ctx.accounts.price_accumulator.as_ref(),
+    )?;
+    if !applied {
+        return Ok(());
+    }
+
+    record_signers(
+        ctx.accounts.signer_stats.as_mut(),
+        oracle_state,
+        &verified_oracles,
+    )?;
+    let operator_amount = accrue_operator_rewards(
+        ctx.accounts.operator_registry.as_mut(),
         feed_id,
-        valid_time_stamp,
-        observe_time_stamp,
-        native_fee,
-        apro_token_fee,
-        expire_at,
-        benchmark_price,
-        ask_price,
-        bid_price,
-        config_digest,
-        epoch_and_round,
-        extra_hash,
-    });
+        &verified_oracles,
+    )?;
+    transfer_fees(
+        &ctx.accounts.payer,
+        &ctx.accounts.admin,
+        ctx.accounts.operator_registry.as_ref(),
+        &ctx.accounts.system_program,
+        UPDATE_FEE_LAMPORTS,
+        operator_amount,
+    )?;
 
     Ok(())
 }
+
+/// Applies a verified report to its feed. Checks the feed is not retired,
+/// the report is newer than the stored one, its price ordering and the
+/// feed's rate limit and push policy, then stores it together with the
+/// slot, time, `updater`, `signers` and reason of the update, appends it to
+/// the feed's history and accumulators when those are supplied, and emits
+/// `PriceUpdated`.
+///
+/// Returns `false` if the feed's circuit breaker holds the report back. It
+/// is then not stored and `CircuitBreakerTripped` is emitted instead;
+/// callers must not charge fees or record signers for it.
+pub(crate) fn apply_report(
+    report: &PriceReport,
+    signers: &[[u8; 20]],
+    updater: Pubkey,
+    oracle_state: &OracleState,
+    feed_config: Option<&FeedConfig>,
+    price_feed: &mut PriceFeed,
+    price_history: Option<&AccountLoader<PriceHistory>>,
+    price_accumulator: Option<&AccountLoader<PriceAccumulator>>,
+) -> Result<bool> {
+    if let Some(config) = feed_config {
+        config.require_live(Clock::get()?.unix_timestamp)?;
+    }
+    // The feed, its history and its accumulators only move forward in time.
+    require!(
+        report.valid_time_stamp > u128::from(price_feed.valid_time_stamp),
+        ErrorCode::StaleReport
+    );
+    PriceFeed::validate_ordering(
+        report.benchmark_price,
+        report.ask_price,
+        report.bid_price,
+        oracle_state.price_ordering_tolerance_bps,
+    )?;
+
+    // A fallback price is stamped with the time it was set, so only a report
+    // newer than that gets past the `StaleReport` check and replaces it.
+    let fallback = price_feed.price_source() == PriceSource::Manual;
+    if !fallback {
+        let clock = Clock::get()?;
+        feed_config
+            .map(|config| config.rate_limit)
+            .unwrap_or_default()
+            .check(
+                price_feed.last_update_slot,
+                u128::from(price_feed.valid_time_stamp),
+                clock.slot,
+                report.valid_time_stamp,
+                clock.unix_timestamp,
+            )?;
+    }
+
+    let push_policy = feed_config
+        .map(|config| config.push_policy)
+        .unwrap_or_default();
+    let reason = push_policy.reason(
+        price_feed.benchmark_price,
+        u128::from(price_feed.valid_time_stamp),
+        report.benchmark_price,
+        report.valid_time_stamp,
+    );
+    require!(
+        fallback || reason != UpdateReason::Unscheduled || !push_policy.reject_unscheduled,
+        ErrorCode::UpdateNotDue
+    );
+
+    let previous_state = price_feed.breaker_state();
+    if let Some(deviation_bps) = price_feed.check_circuit_breaker(
+        report.benchmark_price,
+        report.valid_time_stamp,
+        oracle_state.voting_power(signers),
+    ) {
+        emit!(CircuitBreakerTripped {
+            feed_id: report.feed_id,
+            previous_price: price_feed.benchmark_price,
+            reported_price: report.benchmark_price,
+            deviation_bps,
+            action: price_feed.circuit_breaker().action,
+            state: price_feed.breaker_state(),
+        });
+        return Ok(false);
+    }
+    if previous_state != price_feed.breaker_state() {
+        emit!(CircuitBreakerReset {
+            feed_id: report.feed_id,
+            previous_state,
+        });
+    }
+
+    report.store(price_feed)?;
+    price_feed.record_update(updater, oracle_state.signer_bitmap(signers), reason)?;
+    if fallback {
+        price_feed.price_source = PriceSource::Oracle as u8;
+        emit!(FallbackPriceCleared {
+            feed_id: report.feed_id,
+        });
+    }
+
+    if let Some(price_history) = price_history {
+        PriceHistory::push(
+            price_history.as_ref(),
+            PriceHistoryEntry {
+                valid_time_stamp: report.valid_time_stamp,
+                benchmark_price: report.benchmark_price,
+                bid_price: report.bid_price,
+                ask_price: report.ask_price,
+                epoch_and_round: report.epoch_and_round,
+            },
+        )?;
+    }
+
+    if let Some(price_accumulator) = price_accumulator {
+        price_accumulator
+            .load_mut()?
+            .record(report.valid_time_stamp, report.benchmark_price);
+    }
+
+    emit!(PriceUpdated::from(report));
+
+    Ok(true)
+}
+
+/// Records the oracles that signed a verified report in the `OracleState`'s
+/// signer stats, which must be supplied once they are initialized.
+pub(crate) fn record_signers(
+    signer_stats: Option<&mut Account<SignerStats>>,
+    oracle_state: &OracleState,
+    signers: &[[u8; 20]],
+) -> Result<()> {
+    match signer_stats {
+        Some(signer_stats) => {
+            signer_stats.record(oracle_state.oracles(), signers, Clock::get()?.slot);
+            Ok(())
+        }
+        None if oracle_state.signer_stats_enabled() => err!(ErrorCode::SignerStatsRequired),
+        None => Ok(()),
+    }
+}
+
+/// Loads a feed that `init_if_needed` may have just created, initializing
+/// it if so.
+pub(crate) fn load_price_feed<'a>(
+    price_feed: &'a AccountLoader<PriceFeed>,
+) -> Result<RefMut<'a, PriceFeed>> {
+    let created = price_feed.as_ref().try_borrow_data()?[..8] == [0u8; 8];
+    if created {
+        price_feed.load_init()
+    } else {
+        price_feed.load_mut()
+    }
+}
+
+/// Checks a feed created by this instruction is allowed under
+/// `registered_feeds_only` and records it in the feed registry if the
+/// `OracleState` keeps one.
+pub(crate) fn record_new_feed(
+    oracle_state: &OracleState,
+    price_feed: &PriceFeed,
+    feed_config: Option<&FeedConfig>,
+    feed_registry: Option<&mut Account<FeedRegistry>>,
+    feed_registry_page: Option<&mut Account<FeedRegistryPage>>,
+    feed_id: [u8; 32],
+) -> Result<()> {
+    if price_feed.feed_id != [0u8; 32] {
+        return Ok(());
+    }
+
+    require!(
+        !oracle_state.registered_feeds_only() || feed_config.is_some(),
+        ErrorCode::UnregisteredFeed
+    );
+
+    if !oracle_state.feed_registry_enabled() {
+        return Ok(());
+    }
+
+    match (feed_registry, feed_registry_page) {
+        (Some(feed_registry), Some(feed_registry_page)) => {
+            let status = feed_config.map_or(FeedStatus::Active, |config| config.status);
+            feed_registry.record(
+                feed_registry_page,
+                feed_id,
+                Clock::get()?.unix_timestamp,
+                status,
+            )
+        }
+        _ => err!(ErrorCode::FeedRegistryRequired),
+    }
+}
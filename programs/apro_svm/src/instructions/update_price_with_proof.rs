@@ -0,0 +1,199 @@
+use crate::constants::UPDATE_FEE_LAMPORTS;
+use crate::errors::ErrorCode;
+use crate::instructions::update_price::{
+    apply_report, load_price_feed, record_new_feed, record_signers,
+};
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct UpdatePriceWithProof<'info> {
+    #[account(
+        seeds = [b"oracle_state", oracle_state.load_migrated()?.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = admin,
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(
+        seeds = [
+            b"merkle_root",
+            oracle_state.key().as_ref(),
+            merkle_root.root.as_ref()
+        ],
+        bump
+    )]
+    pub merkle_root: Account<'info, MerkleRoot>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PriceFeed::LEN,
+        seeds = [
+            b"price_feed",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_feed: AccountLoader<'info, PriceFeed>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub admin: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"operator_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub operator_registry: Option<Account<'info, OperatorRegistry>>,
+    #[account(
+        mut,
+        seeds = [
+            b"price_history",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_history: Option<AccountLoader<'info, PriceHistory>>,
+    #[account(
+        mut,
+        seeds = [
+            b"price_accumulator",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub price_accumulator: Option<AccountLoader<'info, PriceAccumulator>>,
+    /// Required when a feed is created under an `OracleState` with a feed
+    /// registry; the new feed is recorded in the registry's current page.
+    #[account(
+        mut,
+        seeds = [b"feed_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+    #[account(
+        mut,
+        seeds = [
+            b"feed_registry_page",
+            oracle_state.key().as_ref(),
+            feed_registry_page.page_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub feed_registry_page: Option<Account<'info, FeedRegistryPage>>,
+    /// CHECK: The feed's `FeedConfig` PDA, which may not exist yet. Loaded
+    /// with `FeedConfig::load`; its quorum override applies and, under
+    /// `registered_feeds_only`, it must exist to create the feed.
+    #[account(
+        seeds = [
+            b"feed_config",
+            oracle_state.key().as_ref(),
+            feed_id.as_ref()
+        ],
+        bump
+    )]
+    pub feed_config: UncheckedAccount<'info>,
+    /// Records which oracles signed the report. Required once the
+    /// `OracleState`'s signer stats are initialized.
+    #[account(
+        mut,
+        seeds = [b"signer_stats", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub signer_stats: Option<Account<'info, SignerStats>>,
+}
+
+/// Applies one report of a Merkle-batched submission. The report context and
+/// the signers come from the cached root; `proof` links the report to it.
+pub fn handler(
+    ctx: Context<UpdatePriceWithProof>,
+    feed_id: [u8; 32],
+    valid_time_stamp: u128,
+    observe_time_stamp: u128,
+    native_fee: u128,
+    apro_token_fee: u128,
+    expire_at: u128,
+    benchmark_price: u128,
+    ask_price: u128,
+    bid_price: u128,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let merkle_root = &ctx.accounts.merkle_root;
+
+    require!(
+        !merkle_root.is_expired(Clock::get()?.unix_timestamp),
+        ErrorCode::MerkleRootExpired
+    );
+
+    let report = PriceReport {
+        feed_id,
+        valid_time_stamp,
+        observe_time_stamp,
+        native_fee,
+        apro_token_fee,
+        expire_at,
+        benchmark_price,
+        ask_price,
+        bid_price,
+        config_digest: merkle_root.config_digest,
+        epoch_and_round: merkle_root.epoch_and_round,
+        extra_hash: merkle_root.extra_hash,
+        signatures: Vec::new(),
+        recovery_ids: Vec::new(),
+    };
+
+    require!(
+        verify_proof(&report.report_data_hash(), &proof, &merkle_root.root),
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let feed_config = FeedConfig::load(&ctx.accounts.feed_config)?;
+    if let Some(config) = feed_config.as_ref() {
+        config.require_single_attestation()?;
+    }
+    let oracle_state = &ctx.accounts.oracle_state.load()?;
+    let signers = Quorum::new(oracle_state, feed_config.as_ref())
+        .check_signers(oracle_state, &merkle_root.signers)?;
+    let price_feed = &mut load_price_feed(&ctx.accounts.price_feed)?;
+    record_new_feed(
+        oracle_state,
+        price_feed,
+        feed_config.as_ref(),
+        ctx.accounts.feed_registry.as_mut(),
+        ctx.accounts.feed_registry_page.as_mut(),
+        feed_id,
+    )?;
+
+    let applied = apply_report(
+        &report,
+        &signers,
+        ctx.accounts.payer.key(),
+        oracle_state,
+        feed_config.as_ref(),
+        price_feed,
+        ctx.accounts.price_history.as_ref(),
+        ctx.accounts.price_accumulator.as_ref(),
+    )?;
+    if !applied {
+        return Ok(());
+    }
+
+    record_signers(ctx.accounts.signer_stats.as_mut(), oracle_state, &signers)?;
+    let operator_amount =
+        accrue_operator_rewards(ctx.accounts.operator_registry.as_mut(), feed_id, &signers)?;
+    transfer_fees(
+        &ctx.accounts.payer,
+        &ctx.accounts.admin,
+        ctx.accounts.operator_registry.as_ref(),
+        &ctx.accounts.system_program,
+        UPDATE_FEE_LAMPORTS,
+        operator_amount,
+    )?;
+
+    Ok(())
+}
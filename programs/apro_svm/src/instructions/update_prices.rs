@@ -0,0 +1,156 @@
+use crate::constants::UPDATE_FEE_LAMPORTS;
+use crate::errors::ErrorCode;
+use crate::instructions::update_price::{apply_report, record_signers};
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_lang::ZeroCopy;
+
+#[derive(Accounts)]
+pub struct UpdatePrices<'info> {
+    #[account(
+        seeds = [b"oracle_state", oracle_state.load_migrated()?.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = admin,
+    )]
+    pub oracle_state: AccountLoader<'info, OracleState>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub admin: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"operator_registry", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub operator_registry: Option<Account<'info, OperatorRegistry>>,
+    /// Records which oracles signed the report. Required once the
+    /// `OracleState`'s signer stats are initialized.
+    #[account(
+        mut,
+        seeds = [b"signer_stats", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub signer_stats: Option<Account<'info, SignerStats>>,
+}
+
+/// Applies a batch of reports. `remaining_accounts` must hold, for each
+/// report and in the same order, its existing writable `PriceFeed` PDA
+/// followed by its `FeedConfig` PDA and its writable `PriceHistory` and
+/// `PriceAccumulator` PDAs, any of which may not exist. Histories and
+/// accumulators that exist are advanced as by `update_price`. Feeds are not
+/// created here; use `update_price` for that.
+///
+/// Returns, for each report, whether it was applied; reports held back by
+/// their feed's circuit breaker are not charged for.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdatePrices<'info>>,
+    reports: Vec<PriceReport>,
+) -> Result<Vec<bool>> {
+    require!(!reports.is_empty(), ErrorCode::EmptyBatch);
+    require!(
+        ctx.remaining_accounts.len() == reports.len() * 4,
+        ErrorCode::PriceFeedAccountMismatch
+    );
+
+    let oracle_state_key = ctx.accounts.oracle_state.key();
+    let mut operator_amount = 0u64;
+    let mut applied = Vec::with_capacity(reports.len());
+
+    for (report, accounts) in reports.iter().zip(ctx.remaining_accounts.chunks(4)) {
+        let [price_feed_info, feed_config_info, price_history_info, price_accumulator_info] =
+            accounts
+        else {
+            unreachable!()
+        };
+        for (account, seed) in [
+            (price_feed_info, b"price_feed".as_ref()),
+            (feed_config_info, b"feed_config".as_ref()),
+            (price_history_info, b"price_history".as_ref()),
+            (price_accumulator_info, b"price_accumulator".as_ref()),
+        ] {
+            let (expected, _) = Pubkey::find_program_address(
+                &[seed, oracle_state_key.as_ref(), report.feed_id.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(account.key(), expected, ErrorCode::PriceFeedAccountMismatch);
+        }
+        require!(
+            price_feed_info.is_writable,
+            ErrorCode::PriceFeedAccountMismatch
+        );
+        require_migrated::<PriceFeed>(price_feed_info)?;
+
+        let price_feed = AccountLoader::<PriceFeed>::try_from(price_feed_info)?;
+        let price_history = load_if_exists::<PriceHistory>(price_history_info)?;
+        let price_accumulator = load_if_exists::<PriceAccumulator>(price_accumulator_info)?;
+
+        let feed_config = FeedConfig::load(feed_config_info)?;
+        if let Some(config) = feed_config.as_ref() {
+            config.require_single_attestation()?;
+        }
+        let oracle_state = &ctx.accounts.oracle_state.load()?;
+        let verified_oracles = report.verify(
+            oracle_state,
+            &Quorum::new(oracle_state, feed_config.as_ref()),
+        )?;
+        let report_applied = apply_report(
+            report,
+            &verified_oracles,
+            ctx.accounts.payer.key(),
+            oracle_state,
+            feed_config.as_ref(),
+            &mut *price_feed.load_mut()?,
+            price_history.as_ref(),
+            price_accumulator.as_ref(),
+        )?;
+        applied.push(report_applied);
+        if !report_applied {
+            continue;
+        }
+
+        record_signers(
+            ctx.accounts.signer_stats.as_mut(),
+            oracle_state,
+            &verified_oracles,
+        )?;
+        operator_amount = operator_amount
+            .checked_add(accrue_operator_rewards(
+                ctx.accounts.operator_registry.as_mut(),
+                report.feed_id,
+                &verified_oracles,
+            )?)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    let applied_count = applied.iter().filter(|applied| **applied).count();
+    if applied_count > 0 {
+        let total_fee = UPDATE_FEE_LAMPORTS
+            .checked_mul(applied_count as u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        transfer_fees(
+            &ctx.accounts.payer,
+            &ctx.accounts.admin,
+            ctx.accounts.operator_registry.as_ref(),
+            &ctx.accounts.system_program,
+            total_fee,
+            operator_amount,
+        )?;
+    }
+
+    Ok(applied)
+}
+
+/// Opens a writable zero-copy account passed at its PDA, or returns `None`
+/// if it has not been created.
+fn load_if_exists<'info, T: ZeroCopy + Owner>(
+    account: &'info AccountInfo<'info>,
+) -> Result<Option<AccountLoader<'info, T>>> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+
+    require!(account.is_writable, ErrorCode::PriceFeedAccountMismatch);
+    Ok(Some(AccountLoader::try_from(account)?))
+}
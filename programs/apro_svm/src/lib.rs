@@ -1,5 +1,9 @@
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
 use instructions::*;
+use states::*;
+use utils::*;
 
 declare_id!("AfeSbLSZ8zMVTVBj4ALAHAbE6VnfM6s9ThcWETKKotkq");
 
@@ -7,6 +11,7 @@ pub mod constants;
 pub mod errors;
 pub mod instructions;
 pub mod states;
+pub mod utils;
 
 #[program]
 pub mod apro_svm {
@@ -29,6 +34,70 @@ pub mod apro_svm {
         instructions::add_oracle::handler(ctx, oracle)
     }
 
+    pub fn initialize_operator_registry(
+        ctx: Context<InitializeOperatorRegistry>,
+        operator_fee_bps: u64,
+    ) -> Result<()> {
+        instructions::initialize_operator_registry::handler(ctx, operator_fee_bps)
+    }
+
+    pub fn set_operator(ctx: Context<SetOperator>, oracle: [u8; 20], payout: Pubkey) -> Result<()> {
+        instructions::set_operator::handler(ctx, oracle, payout)
+    }
+
+    pub fn set_operator_fee_share(
+        ctx: Context<SetOperatorFeeShare>,
+        operator_fee_bps: u64,
+    ) -> Result<()> {
+        instructions::set_operator_fee_share::handler(ctx, operator_fee_bps)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, oracle: [u8; 20]) -> Result<()> {
+        instructions::claim_rewards::handler(ctx, oracle)
+    }
+
+    pub fn initialize_price_history(
+        ctx: Context<InitializePriceHistory>,
+        feed_id: [u8; 32],
+        capacity: u64,
+    ) -> Result<()> {
+        instructions::initialize_price_history::handler(ctx, feed_id, capacity)
+    }
+
+    pub fn initialize_price_accumulator(
+        ctx: Context<InitializePriceAccumulator>,
+        feed_id: [u8; 32],
+        ema_period: u64,
+    ) -> Result<()> {
+        instructions::initialize_price_accumulator::handler(ctx, feed_id, ema_period)
+    }
+
+    pub fn set_feed_config(
+        ctx: Context<SetFeedConfig>,
+        feed_id: [u8; 32],
+        decimals: u8,
+        base_symbol: String,
+        quote_symbol: String,
+        description: String,
+    ) -> Result<()> {
+        instructions::set_feed_config::handler(
+            ctx,
+            feed_id,
+            decimals,
+            base_symbol,
+            quote_symbol,
+            description,
+        )
+    }
+
+    pub fn set_feed_status(
+        ctx: Context<SetFeedStatus>,
+        feed_id: [u8; 32],
+        status: FeedStatus,
+    ) -> Result<()> {
+        instructions::set_feed_status::handler(ctx, feed_id, status)
+    }
+
     pub fn update_price(
         ctx: Context<UpdatePrice>,
         feed_id: [u8; 32],
@@ -64,4 +133,299 @@ pub mod apro_svm {
             recovery_ids,
         )
     }
+    pub fn update_prices<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdatePrices<'info>>,
+        reports: Vec<PriceReport>,
+    ) -> Result<Vec<bool>> {
+        instructions::update_prices::handler(ctx, reports)
+    }
+
+    pub fn submit_merkle_root(
+        ctx: Context<SubmitMerkleRoot>,
+        root: [u8; 32],
+        config_digest: [u8; 32],
+        epoch_and_round: u128,
+        extra_hash: [u8; 32],
+        signatures: Vec<[u8; 64]>,
+        recovery_ids: Vec<u8>,
+    ) -> Result<()> {
+        instructions::submit_merkle_root::handler(
+            ctx,
+            root,
+            config_digest,
+            epoch_and_round,
+            extra_hash,
+            signatures,
+            recovery_ids,
+        )
+    }
+
+    pub fn update_price_with_proof(
+        ctx: Context<UpdatePriceWithProof>,
+        feed_id: [u8; 32],
+        valid_time_stamp: u128,
+        observe_time_stamp: u128,
+        native_fee: u128,
+        apro_token_fee: u128,
+        expire_at: u128,
+        benchmark_price: u128,
+        ask_price: u128,
+        bid_price: u128,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::update_price_with_proof::handler(
+            ctx,
+            feed_id,
+            valid_time_stamp,
+            observe_time_stamp,
+            native_fee,
+            apro_token_fee,
+            expire_at,
+            benchmark_price,
+            ask_price,
+            bid_price,
+            proof,
+        )
+    }
+
+    pub fn close_merkle_root(ctx: Context<CloseMerkleRoot>) -> Result<()> {
+        instructions::close_merkle_root::handler(ctx)
+    }
+
+    pub fn initialize_feed_registry(ctx: Context<InitializeFeedRegistry>) -> Result<()> {
+        instructions::initialize_feed_registry::handler(ctx)
+    }
+
+    pub fn add_feed_registry_page(ctx: Context<AddFeedRegistryPage>) -> Result<()> {
+        instructions::add_feed_registry_page::handler(ctx)
+    }
+
+    pub fn set_registered_feeds_only(
+        ctx: Context<SetRegisteredFeedsOnly>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_registered_feeds_only::handler(ctx, enabled)
+    }
+
+    pub fn set_feed_kind(
+        ctx: Context<SetFeedKind>,
+        feed_id: [u8; 32],
+        kind: FeedKind,
+        field_names: Vec<String>,
+    ) -> Result<()> {
+        instructions::set_feed_kind::handler(ctx, feed_id, kind, field_names)
+    }
+
+    pub fn update_data_feed(ctx: Context<UpdateDataFeed>, report: DataReport) -> Result<()> {
+        instructions::update_data_feed::handler(ctx, report)
+    }
+
+    pub fn set_price_ordering_tolerance(
+        ctx: Context<SetPriceOrderingTolerance>,
+        tolerance_bps: u64,
+    ) -> Result<()> {
+        instructions::set_price_ordering_tolerance::handler(ctx, tolerance_bps)
+    }
+
+    pub fn set_circuit_breaker(
+        ctx: Context<SetCircuitBreaker>,
+        feed_id: [u8; 32],
+        circuit_breaker: CircuitBreaker,
+    ) -> Result<()> {
+        instructions::set_circuit_breaker::handler(ctx, feed_id, circuit_breaker)
+    }
+
+    pub fn acknowledge_circuit_breaker(
+        ctx: Context<AcknowledgeCircuitBreaker>,
+        feed_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::acknowledge_circuit_breaker::handler(ctx, feed_id)
+    }
+
+    pub fn set_feed_quorum(
+        ctx: Context<SetFeedQuorum>,
+        feed_id: [u8; 32],
+        required_signatures: u64,
+        weight_threshold: u64,
+        allowed_oracles: Vec<[u8; 20]>,
+    ) -> Result<()> {
+        instructions::set_feed_quorum::handler(
+            ctx,
+            feed_id,
+            required_signatures,
+            weight_threshold,
+            allowed_oracles,
+        )
+    }
+
+    pub fn add_weighted_oracle(
+        ctx: Context<AddWeightedOracle>,
+        oracle: [u8; 20],
+        weight: u64,
+    ) -> Result<()> {
+        instructions::add_weighted_oracle::handler(ctx, oracle, weight)
+    }
+
+    pub fn set_oracle_weight(
+        ctx: Context<SetOracleWeight>,
+        oracle: [u8; 20],
+        weight: u64,
+    ) -> Result<()> {
+        instructions::set_oracle_weight::handler(ctx, oracle, weight)
+    }
+
+    pub fn rotate_oracle(
+        ctx: Context<RotateOracle>,
+        old_oracle: [u8; 20],
+        new_oracle: [u8; 20],
+        weight: u64,
+    ) -> Result<()> {
+        instructions::rotate_oracle::handler(ctx, old_oracle, new_oracle, weight)
+    }
+
+    pub fn set_quorum_mode(
+        ctx: Context<SetQuorumMode>,
+        quorum_mode: QuorumMode,
+        weight_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_quorum_mode::handler(ctx, quorum_mode, weight_threshold)
+    }
+
+    pub fn set_secondary_oracle_state(
+        ctx: Context<SetSecondaryOracleState>,
+        feed_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_secondary_oracle_state::handler(ctx, feed_id)
+    }
+
+    pub fn set_aggregate_feed(
+        ctx: Context<SetAggregateFeed>,
+        feed_id: [u8; 32],
+        oracle_states: Vec<Pubkey>,
+        max_staleness: u64,
+        min_sources: u8,
+    ) -> Result<()> {
+        instructions::set_aggregate_feed::handler(
+            ctx,
+            feed_id,
+            oracle_states,
+            max_staleness,
+            min_sources,
+        )
+    }
+
+    pub fn aggregate_feed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AggregateFeedPrices<'info>>,
+    ) -> Result<()> {
+        instructions::aggregate_feed::handler(ctx)
+    }
+
+    pub fn set_derived_feed(
+        ctx: Context<SetDerivedFeed>,
+        derived_id: [u8; 32],
+        formula: DerivedFormula,
+        base_feed_id: [u8; 32],
+        quote_feed_id: Option<[u8; 32]>,
+        decimals: u8,
+        max_staleness: u64,
+    ) -> Result<()> {
+        instructions::set_derived_feed::handler(
+            ctx,
+            derived_id,
+            formula,
+            base_feed_id,
+            quote_feed_id,
+            decimals,
+            max_staleness,
+        )
+    }
+
+    pub fn refresh_derived_feed(ctx: Context<RefreshDerivedFeed>) -> Result<()> {
+        instructions::refresh_derived_feed::handler(ctx)
+    }
+
+    pub fn set_basket_feed(
+        ctx: Context<SetBasketFeed>,
+        basket_id: [u8; 32],
+        constituents: Vec<BasketWeight>,
+        decimals: u8,
+        max_staleness: u64,
+    ) -> Result<()> {
+        instructions::set_basket_feed::handler(
+            ctx,
+            basket_id,
+            constituents,
+            decimals,
+            max_staleness,
+        )
+    }
+
+    pub fn refresh_basket_feed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshBasketFeed<'info>>,
+    ) -> Result<()> {
+        instructions::refresh_basket_feed::handler(ctx)
+    }
+
+    pub fn migrate_oracle_state(ctx: Context<MigrateOracleState>) -> Result<()> {
+        instructions::migrate_oracle_state::handler(ctx)
+    }
+
+    pub fn migrate_price_feed(ctx: Context<MigratePriceFeed>, feed_id: [u8; 32]) -> Result<()> {
+        instructions::migrate_price_feed::handler(ctx, feed_id)
+    }
+
+    pub fn initialize_signer_stats(ctx: Context<InitializeSignerStats>) -> Result<()> {
+        instructions::initialize_signer_stats::handler(ctx)
+    }
+
+    pub fn set_push_policy(
+        ctx: Context<SetPushPolicy>,
+        feed_id: [u8; 32],
+        push_policy: PushPolicy,
+    ) -> Result<()> {
+        instructions::set_push_policy::handler(ctx, feed_id, push_policy)
+    }
+
+    pub fn set_rate_limit(
+        ctx: Context<SetRateLimit>,
+        feed_id: [u8; 32],
+        min_interval_slots: u64,
+        min_interval_seconds: u64,
+    ) -> Result<()> {
+        instructions::set_rate_limit::handler(
+            ctx,
+            feed_id,
+            min_interval_slots,
+            min_interval_seconds,
+        )
+    }
+
+    pub fn override_rate_limit(
+        ctx: Context<OverrideRateLimit>,
+        feed_id: [u8; 32],
+        override_until: i64,
+    ) -> Result<()> {
+        instructions::override_rate_limit::handler(ctx, feed_id, override_until)
+    }
+
+    pub fn set_pauser(ctx: Context<SetPauser>, pauser: Pubkey) -> Result<()> {
+        instructions::set_pauser::handler(ctx, pauser)
+    }
+
+    pub fn set_fallback_price(
+        ctx: Context<SetFallbackPrice>,
+        feed_id: [u8; 32],
+        price: u128,
+        duration: i64,
+    ) -> Result<()> {
+        instructions::set_fallback_price::handler(ctx, feed_id, price, duration)
+    }
+
+    pub fn set_feed_sunset(
+        ctx: Context<SetFeedSunset>,
+        feed_id: [u8; 32],
+        sunset_at: i64,
+    ) -> Result<()> {
+        instructions::set_feed_sunset::handler(ctx, feed_id, sunset_at)
+    }
 }
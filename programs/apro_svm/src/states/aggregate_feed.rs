@@ -0,0 +1,83 @@
+use crate::constants::MAX_AGGREGATE_SOURCES;
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Median of one feed across several oracle states, maintained by the
+/// permissionless `aggregate_feed` crank.
+#[account]
+#[derive(InitSpace)]
+pub struct AggregateFeed {
+    pub authority: Pubkey,
+    pub feed_id: [u8; 32],
+    /// `PriceFeed` PDAs of `feed_id` under each source oracle state.
+    #[max_len(MAX_AGGREGATE_SOURCES)]
+    pub sources: Vec<Pubkey>,
+    /// `FeedConfig` PDAs of `feed_id` under each source oracle state.
+    #[max_len(MAX_AGGREGATE_SOURCES)]
+    pub feed_configs: Vec<Pubkey>,
+    /// Sources whose `valid_time_stamp` is older than this many seconds are
+    /// left out.
+    pub max_staleness: u64,
+    pub min_sources: u8,
+    pub median_price: u128,
+    pub min_price: u128,
+    pub max_price: u128,
+    pub source_count: u8,
+    /// Oldest `valid_time_stamp` among the sources used.
+    pub oldest_time_stamp: u128,
+    pub updated_at: i64,
+}
+
+impl AggregateFeed {
+    pub fn configure(
+        &mut self,
+        authority: Pubkey,
+        feed_id: [u8; 32],
+        sources: Vec<Pubkey>,
+        feed_configs: Vec<Pubkey>,
+        max_staleness: u64,
+        min_sources: u8,
+    ) -> Result<()> {
+        require!(
+            !sources.is_empty()
+                && sources.len() <= MAX_AGGREGATE_SOURCES
+                && sources
+                    .iter()
+                    .enumerate()
+                    .all(|(index, source)| !sources[..index].contains(source))
+                && min_sources > 0
+                && usize::from(min_sources) <= sources.len()
+                && max_staleness > 0,
+            ErrorCode::InvalidAggregateSources
+        );
+
+        self.authority = authority;
+        self.feed_id = feed_id;
+        self.sources = sources;
+        self.feed_configs = feed_configs;
+        self.max_staleness = max_staleness;
+        self.min_sources = min_sources;
+        Ok(())
+    }
+
+    /// Stores the median, min and max of `prices`, given as
+    /// `(benchmark_price, valid_time_stamp)` of each fresh source.
+    pub fn record(&mut self, mut prices: Vec<(u128, u128)>, now: i64) -> Result<()> {
+        require!(
+            prices.len() >= usize::from(self.min_sources),
+            ErrorCode::InsufficientAggregateSources
+        );
+
+        prices.sort_unstable();
+        // The two middle prices coincide when the count is odd.
+        let low = prices[(prices.len() - 1) / 2].0;
+        let high = prices[prices.len() / 2].0;
+        self.median_price = low + (high - low) / 2;
+        self.min_price = prices[0].0;
+        self.max_price = prices[prices.len() - 1].0;
+        self.source_count = prices.len() as u8;
+        self.oldest_time_stamp = prices.iter().map(|(_, time)| *time).min().unwrap_or(0);
+        self.updated_at = now;
+        Ok(())
+    }
+}
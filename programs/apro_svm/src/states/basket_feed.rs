@@ -0,0 +1,92 @@
+use crate::constants::{BASKET_WEIGHT_DECIMALS, MAX_BASKET_CONSTITUENTS, MAX_DECIMALS};
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use ethabi::Uint;
+
+/// A constituent as given to `set_basket_feed`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BasketWeight {
+    pub feed_id: [u8; 32],
+    /// Weight with `BASKET_WEIGHT_DECIMALS` decimals.
+    pub weight: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct BasketConstituent {
+    pub price_feed: Pubkey,
+    /// `FeedConfig` PDA of the feed, giving its decimals.
+    pub feed_config: Pubkey,
+    pub weight: u64,
+    /// `valid_time_stamp` of the price used for the current value.
+    pub valid_time_stamp: u128,
+}
+
+/// A weighted sum of price feeds of one oracle state, refreshed by the
+/// permissionless `refresh_basket_feed` instruction.
+#[account]
+#[derive(InitSpace)]
+pub struct BasketFeed {
+    pub oracle_state: Pubkey,
+    pub basket_id: [u8; 32],
+    #[max_len(MAX_BASKET_CONSTITUENTS)]
+    pub constituents: Vec<BasketConstituent>,
+    pub decimals: u8,
+    /// Refreshing fails if a constituent is older than this many seconds.
+    pub max_staleness: u64,
+    pub value: u128,
+    pub updated_at: i64,
+}
+
+impl BasketFeed {
+    pub fn configure(
+        &mut self,
+        oracle_state: Pubkey,
+        basket_id: [u8; 32],
+        constituents: Vec<BasketConstituent>,
+        decimals: u8,
+        max_staleness: u64,
+    ) -> Result<()> {
+        require!(decimals <= MAX_DECIMALS, ErrorCode::InvalidDecimals);
+        require!(
+            !constituents.is_empty()
+                && constituents.len() <= MAX_BASKET_CONSTITUENTS
+                && constituents
+                    .iter()
+                    .all(|constituent| constituent.weight > 0)
+                && max_staleness > 0,
+            ErrorCode::InvalidBasketFeed
+        );
+
+        self.oracle_state = oracle_state;
+        self.basket_id = basket_id;
+        self.constituents = constituents;
+        self.decimals = decimals;
+        self.max_staleness = max_staleness;
+        self.value = 0;
+        self.updated_at = 0;
+        Ok(())
+    }
+
+    /// Computes the basket value from the constituents' benchmark prices
+    /// and decimals, in order, truncating each weighted term towards zero.
+    pub fn compute(&self, prices: &[(u128, u8)]) -> Result<u128> {
+        let mut value = Uint::zero();
+        for (constituent, (price, price_decimals)) in self.constituents.iter().zip(prices) {
+            // term = price * weight * 10^(decimals - price decimals - weight decimals)
+            let exponent = i32::from(self.decimals)
+                - i32::from(*price_decimals)
+                - i32::from(BASKET_WEIGHT_DECIMALS);
+            let scale = Uint::exp10(exponent.unsigned_abs() as usize);
+            let product = Uint::from(*price) * Uint::from(constituent.weight);
+            let term = if exponent >= 0 {
+                product.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?
+            } else {
+                product / scale
+            };
+            value = value.checked_add(term).ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        require!(value <= Uint::from(u128::MAX), ErrorCode::MathOverflow);
+        Ok(value.as_u128())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::constants::BPS_DENOMINATOR;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum BreakerAction {
+    /// Discard reports beyond the limits until one is within them again or
+    /// the admin acknowledges the move.
+    Reject,
+    /// As `Reject`, but a report carrying `confirmation_signatures` also
+    /// releases the feed.
+    RequireConfirmation,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum BreakerState {
+    Normal,
+    /// A report beyond the limits tripped the breaker; reports beyond them
+    /// are held back until the feed is released.
+    NeedsConfirmation,
+    /// The admin accepted the move; the next report is applied unchecked.
+    Acknowledged,
+}
+
+/// Per-feed limits on how far a report may move `benchmark_price` from the
+/// previous one. A zero limit disables that check.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct CircuitBreaker {
+    pub max_deviation_bps: u64,
+    pub max_rate_bps_per_sec: u64,
+    pub action: BreakerAction,
+    /// Signatures a report needs to confirm a move, or their summed weight
+    /// when the oracle state is in weight mode.
+    pub confirmation_signatures: u64,
+}
+
+impl CircuitBreaker {
+    pub fn is_enabled(&self) -> bool {
+        self.max_deviation_bps > 0 || self.max_rate_bps_per_sec > 0
+    }
+
+    /// Returns the deviation of `price` from `previous_price` in bps if it
+    /// exceeds a limit, `elapsed` being the seconds between both reports.
+    pub fn exceeded(&self, previous_price: u128, price: u128, elapsed: u128) -> Option<u64> {
+        if !self.is_enabled() || previous_price == 0 {
+            return None;
+        }
+
+        let deviation_bps = u64::try_from(
+            previous_price
+                .abs_diff(price)
+                .saturating_mul(BPS_DENOMINATOR as u128)
+                / previous_price,
+        )
+        .unwrap_or(u64::MAX);
+
+        let over_deviation = self.max_deviation_bps > 0 && deviation_bps > self.max_deviation_bps;
+        let max_rate_move = u128::from(self.max_rate_bps_per_sec).saturating_mul(elapsed.max(1));
+        let over_rate = self.max_rate_bps_per_sec > 0 && u128::from(deviation_bps) > max_rate_move;
+
+        (over_deviation || over_rate).then_some(deviation_bps)
+    }
+}
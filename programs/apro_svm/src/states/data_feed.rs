@@ -0,0 +1,20 @@
+use crate::constants::MAX_DATA_FIELDS;
+use anchor_lang::prelude::*;
+
+/// Latest report of a `Signed` or `Data` feed. `values` follow the field
+/// order of the feed's `FeedConfig`.
+#[account]
+#[derive(InitSpace)]
+pub struct DataFeed {
+    pub feed_id: [u8; 32],
+    pub valid_time_stamp: u128,
+    pub observe_time_stamp: u128,
+    pub native_fee: u128,
+    pub apro_token_fee: u128,
+    pub expire_at: u128,
+    #[max_len(MAX_DATA_FIELDS)]
+    pub values: Vec<i128>,
+    pub config_digest: [u8; 32],
+    pub epoch_and_round: u128,
+    pub extra_hash: [u8; 32],
+}
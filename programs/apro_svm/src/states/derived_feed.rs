@@ -0,0 +1,114 @@
+use crate::constants::MAX_DECIMALS;
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use ethabi::Uint;
+
+/// How a derived feed combines its inputs, each taken with the decimals of
+/// its `FeedConfig`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum DerivedFormula {
+    /// `1 / base`, e.g. USD/SOL from SOL/USD.
+    Invert,
+    /// `base * quote`.
+    Product,
+    /// `base / quote`, e.g. ETH/BTC from ETH/USD and BTC/USD.
+    Quotient,
+}
+
+/// A price computed from other `PriceFeed`s of the same oracle state and
+/// refreshed by the permissionless `refresh_derived_feed` instruction.
+#[account]
+#[derive(InitSpace)]
+pub struct DerivedFeed {
+    pub oracle_state: Pubkey,
+    pub derived_id: [u8; 32],
+    pub formula: DerivedFormula,
+    pub base_feed: Pubkey,
+    /// `FeedConfig` PDA of the base feed, giving its decimals.
+    pub base_feed_config: Pubkey,
+    /// Unused by `Invert`.
+    pub quote_feed: Option<Pubkey>,
+    pub quote_feed_config: Option<Pubkey>,
+    pub decimals: u8,
+    /// Refreshing fails if an input is older than this many seconds.
+    pub max_staleness: u64,
+    pub price: u128,
+    /// Oldest `valid_time_stamp` among the inputs of `price`.
+    pub oldest_time_stamp: u128,
+    pub updated_at: i64,
+}
+
+impl DerivedFeed {
+    pub fn configure(
+        &mut self,
+        oracle_state: Pubkey,
+        derived_id: [u8; 32],
+        formula: DerivedFormula,
+        base_feed: Pubkey,
+        base_feed_config: Pubkey,
+        quote_feed: Option<Pubkey>,
+        quote_feed_config: Option<Pubkey>,
+        decimals: u8,
+        max_staleness: u64,
+    ) -> Result<()> {
+        require!(decimals <= MAX_DECIMALS, ErrorCode::InvalidDecimals);
+        require!(
+            (formula == DerivedFormula::Invert) == quote_feed.is_none()
+                && quote_feed.is_none() == quote_feed_config.is_none()
+                && max_staleness > 0,
+            ErrorCode::InvalidDerivedFeed
+        );
+
+        self.oracle_state = oracle_state;
+        self.derived_id = derived_id;
+        self.formula = formula;
+        self.base_feed = base_feed;
+        self.base_feed_config = base_feed_config;
+        self.quote_feed = quote_feed;
+        self.quote_feed_config = quote_feed_config;
+        self.decimals = decimals;
+        self.max_staleness = max_staleness;
+        Ok(())
+    }
+
+    /// Computes the derived price from the inputs' benchmark prices and
+    /// decimals, truncating towards zero.
+    pub fn compute(
+        &self,
+        base_price: u128,
+        base_decimals: u8,
+        quote_price: u128,
+        quote_decimals: u8,
+    ) -> Result<u128> {
+        let decimals = i32::from(self.decimals);
+        let base_decimals = i32::from(base_decimals);
+        let quote_decimals = i32::from(quote_decimals);
+        let base = Uint::from(base_price);
+        let quote = Uint::from(quote_price);
+
+        // value = numerator / denominator * 10^exponent
+        let (numerator, denominator, exponent) = match self.formula {
+            DerivedFormula::Invert => (Uint::one(), base, decimals + base_decimals),
+            DerivedFormula::Product => (
+                base.checked_mul(quote).ok_or(ErrorCode::MathOverflow)?,
+                Uint::one(),
+                decimals - base_decimals - quote_decimals,
+            ),
+            DerivedFormula::Quotient => (base, quote, decimals - base_decimals + quote_decimals),
+        };
+        require!(!denominator.is_zero(), ErrorCode::InvalidDerivedInput);
+
+        let scale = Uint::exp10(exponent.unsigned_abs() as usize);
+        let value = if exponent >= 0 {
+            numerator
+                .checked_mul(scale)
+                .ok_or(ErrorCode::MathOverflow)?
+                / denominator
+        } else {
+            numerator / denominator / scale
+        };
+
+        require!(value <= Uint::from(u128::MAX), ErrorCode::MathOverflow);
+        Ok(value.as_u128())
+    }
+}
@@ -0,0 +1,236 @@
+use crate::constants::{
+    MAX_DATA_FIELDS, MAX_DECIMALS, MAX_DESCRIPTION_LEN, MAX_FIELD_NAME_LEN, MAX_ORACLES,
+    MAX_SYMBOL_LEN,
+};
+use crate::errors::ErrorCode;
+use crate::states::{OracleState, PushPolicy, RateLimit};
+use anchor_lang::prelude::*;
+
+/// Lifecycle of a feed. A `Deprecated` feed keeps updating until its
+/// `sunset_at`, if set, after which it counts as `Retired`. `Retired` feeds
+/// no longer update and cannot be reactivated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum FeedStatus {
+    Active,
+    Deprecated,
+    Retired,
+}
+
+/// What a feed reports. `Price` feeds are updated through `update_price`;
+/// `Signed` (one value) and `Data` (one value per named field) feeds are
+/// updated through `update_data_feed` and hold `i128` values.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum FeedKind {
+    Price,
+    Signed,
+    Data,
+}
+
+/// Admin managed metadata describing how to interpret a feed's raw values.
+#[account]
+#[derive(InitSpace)]
+pub struct FeedConfig {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub decimals: u8,
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub base_symbol: String,
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub quote_symbol: String,
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub description: String,
+    pub status: FeedStatus,
+    pub kind: FeedKind,
+    #[max_len(MAX_DATA_FIELDS, MAX_FIELD_NAME_LEN)]
+    pub field_names: Vec<String>,
+    /// Overrides the oracle state's `required_signatures` when non-zero. In
+    /// weight mode it is the minimum number of distinct signers.
+    pub required_signatures: u64,
+    /// Overrides the oracle state's `weight_threshold` in weight mode when
+    /// non-zero.
+    pub weight_threshold: u64,
+    /// When non-empty, only these oracles count towards the feed's quorum.
+    #[max_len(MAX_ORACLES)]
+    pub allowed_oracles: Vec<[u8; 20]>,
+    /// When set, `update_price` also requires the quorum of this second
+    /// `OracleState` over the same report.
+    pub secondary_oracle_state: Option<Pubkey>,
+    pub push_policy: PushPolicy,
+    pub rate_limit: RateLimit,
+    /// When the `Deprecated` feed retires, or 0 if no date is set.
+    pub sunset_at: i64,
+}
+
+impl FeedConfig {
+    /// Loads the config stored at the feed's `FeedConfig` PDA, or `None` if
+    /// the admin has not created it.
+    pub fn load(account: &AccountInfo) -> Result<Option<FeedConfig>> {
+        if account.owner != &crate::ID || account.data_is_empty() {
+            return Ok(None);
+        }
+
+        let data = account.try_borrow_data()?;
+        Ok(Some(FeedConfig::try_deserialize(&mut &data[..])?))
+    }
+
+    pub fn update_metadata(
+        &mut self,
+        oracle_state: Pubkey,
+        feed_id: [u8; 32],
+        decimals: u8,
+        base_symbol: String,
+        quote_symbol: String,
+        description: String,
+    ) -> Result<()> {
+        require!(decimals <= MAX_DECIMALS, ErrorCode::InvalidDecimals);
+        require!(
+            base_symbol.len() <= MAX_SYMBOL_LEN && quote_symbol.len() <= MAX_SYMBOL_LEN,
+            ErrorCode::SymbolTooLong
+        );
+        require!(
+            description.len() <= MAX_DESCRIPTION_LEN,
+            ErrorCode::DescriptionTooLong
+        );
+
+        self.oracle_state = oracle_state;
+        self.feed_id = feed_id;
+        self.decimals = decimals;
+        self.base_symbol = base_symbol;
+        self.quote_symbol = quote_symbol;
+        self.description = description;
+        Ok(())
+    }
+
+    /// Moves the feed to `status` at `now`. A feed whose sunset has passed is
+    /// retired and can no longer be moved out of it.
+    pub fn set_status(&mut self, status: FeedStatus, now: i64) -> Result<()> {
+        require!(
+            self.effective_status(now) != FeedStatus::Retired || status == FeedStatus::Retired,
+            ErrorCode::FeedRetired
+        );
+        if status == FeedStatus::Active {
+            self.sunset_at = 0;
+        }
+        self.status = status;
+        Ok(())
+    }
+
+    /// Deprecates the feed, announcing it retires at `sunset_at`.
+    pub fn schedule_sunset(&mut self, sunset_at: i64, now: i64) -> Result<()> {
+        require!(sunset_at > now, ErrorCode::InvalidSunset);
+        self.set_status(FeedStatus::Deprecated, now)?;
+        self.sunset_at = sunset_at;
+        Ok(())
+    }
+
+    /// The feed's status at `now`, once its sunset has passed.
+    pub fn effective_status(&self, now: i64) -> FeedStatus {
+        match self.status {
+            FeedStatus::Deprecated if self.sunset_at != 0 && now >= self.sunset_at => {
+                FeedStatus::Retired
+            }
+            status => status,
+        }
+    }
+
+    /// Fails for feeds that are retired at `now`.
+    pub fn require_live(&self, now: i64) -> Result<()> {
+        require!(
+            self.effective_status(now) != FeedStatus::Retired,
+            ErrorCode::FeedRetired
+        );
+        Ok(())
+    }
+
+    /// Sets the feed kind. `Signed` feeds may name their single value, `Data`
+    /// feeds must name each of their values and `Price` feeds take no names.
+    pub fn set_kind(&mut self, kind: FeedKind, field_names: Vec<String>) -> Result<()> {
+        let max_fields = match kind {
+            FeedKind::Price => 0,
+            FeedKind::Signed => 1,
+            FeedKind::Data => MAX_DATA_FIELDS,
+        };
+        require!(
+            field_names.len() <= max_fields
+                && (kind != FeedKind::Data || !field_names.is_empty())
+                && field_names
+                    .iter()
+                    .all(|name| name.len() <= MAX_FIELD_NAME_LEN),
+            ErrorCode::InvalidFeedFields
+        );
+
+        self.kind = kind;
+        self.field_names = field_names;
+        Ok(())
+    }
+
+    /// Number of values an `update_data_feed` report must carry.
+    pub fn value_count(&self) -> Result<usize> {
+        match self.kind {
+            FeedKind::Price => err!(ErrorCode::FeedKindMismatch),
+            FeedKind::Signed => Ok(1),
+            FeedKind::Data => Ok(self.field_names.len()),
+        }
+    }
+
+    /// Sets the feed's quorum override. `allowed_oracles` must be part of
+    /// the oracle state's oracles and able to reach both thresholds.
+    pub fn set_quorum(
+        &mut self,
+        oracle_state: &OracleState,
+        required_signatures: u64,
+        weight_threshold: u64,
+        allowed_oracles: Vec<[u8; 20]>,
+    ) -> Result<()> {
+        let oracles = oracle_state.oracles();
+        let eligible = if allowed_oracles.is_empty() {
+            oracles
+        } else {
+            &allowed_oracles
+        };
+        require!(
+            allowed_oracles
+                .iter()
+                .all(|oracle| oracles.contains(oracle))
+                && required_signatures as usize <= eligible.len()
+                && weight_threshold <= oracle_state.weight_of_all(eligible),
+            ErrorCode::InvalidFeedQuorum
+        );
+
+        self.required_signatures = required_signatures;
+        self.weight_threshold = weight_threshold;
+        self.allowed_oracles = allowed_oracles;
+        Ok(())
+    }
+
+    pub fn set_secondary_oracle_state(
+        &mut self,
+        secondary_oracle_state: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            secondary_oracle_state != Some(self.oracle_state),
+            ErrorCode::InvalidSecondaryOracleState
+        );
+        self.secondary_oracle_state = secondary_oracle_state;
+        Ok(())
+    }
+
+    pub fn set_push_policy(&mut self, push_policy: PushPolicy) -> Result<()> {
+        require!(
+            push_policy.is_enabled() || !push_policy.reject_unscheduled,
+            ErrorCode::InvalidPushPolicy
+        );
+        self.push_policy = push_policy;
+        Ok(())
+    }
+
+    /// Fails for feeds requiring dual attestation, which only `update_price`
+    /// verifies.
+    pub fn require_single_attestation(&self) -> Result<()> {
+        require!(
+            self.secondary_oracle_state.is_none(),
+            ErrorCode::DualAttestationUnsupported
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::constants::FEEDS_PER_REGISTRY_PAGE;
+use crate::errors::ErrorCode;
+use crate::states::FeedStatus;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RegisteredFeed {
+    pub feed_id: [u8; 32],
+    pub created_at: i64,
+    /// The status stored in the feed's `FeedConfig`, kept in sync by
+    /// `set_feed_status` and `set_feed_sunset`. A `Deprecated` feed retires
+    /// at its `FeedConfig::sunset_at`.
+    pub status: FeedStatus,
+}
+
+/// Index of every feed created under an `OracleState`. Feeds are stored in
+/// `FeedRegistryPage` accounts; new feeds go to the last page.
+#[account]
+#[derive(InitSpace)]
+pub struct FeedRegistry {
+    pub oracle_state: Pubkey,
+    pub page_count: u32,
+    pub feed_count: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeedRegistryPage {
+    pub oracle_state: Pubkey,
+    pub page_index: u32,
+    #[max_len(FEEDS_PER_REGISTRY_PAGE)]
+    pub feeds: Vec<RegisteredFeed>,
+}
+
+impl FeedRegistry {
+    pub fn initialize(&mut self, oracle_state: Pubkey) {
+        self.oracle_state = oracle_state;
+        self.page_count = 1;
+        self.feed_count = 0;
+    }
+
+    /// Appends `feed_id` to `page`, which must be the current page.
+    pub fn record(
+        &mut self,
+        page: &mut FeedRegistryPage,
+        feed_id: [u8; 32],
+        created_at: i64,
+        status: FeedStatus,
+    ) -> Result<()> {
+        require!(
+            page.page_index + 1 == self.page_count,
+            ErrorCode::InvalidFeedRegistryPage
+        );
+        require!(!page.is_full(), ErrorCode::FeedRegistryPageFull);
+
+        page.feeds.push(RegisteredFeed {
+            feed_id,
+            created_at,
+            status,
+        });
+        self.feed_count += 1;
+        Ok(())
+    }
+}
+
+impl FeedRegistryPage {
+    pub fn initialize(&mut self, oracle_state: Pubkey, page_index: u32) {
+        self.oracle_state = oracle_state;
+        self.page_index = page_index;
+        self.feeds = Vec::new();
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.feeds.len() >= FEEDS_PER_REGISTRY_PAGE
+    }
+
+    pub fn set_status(&mut self, feed_id: [u8; 32], status: FeedStatus) -> Result<()> {
+        let entry = self
+            .feeds
+            .iter_mut()
+            .find(|entry| entry.feed_id == feed_id)
+            .ok_or(ErrorCode::FeedNotRegistered)?;
+        entry.status = status;
+        Ok(())
+    }
+}
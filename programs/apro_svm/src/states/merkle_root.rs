@@ -0,0 +1,46 @@
+use crate::constants::MAX_ORACLES;
+use anchor_lang::prelude::*;
+
+/// A DON-signed Merkle root over many feed reports, cached after its quorum
+/// has been verified so each report can be applied with just a proof.
+#[account]
+#[derive(InitSpace)]
+pub struct MerkleRoot {
+    pub oracle_state: Pubkey,
+    pub root: [u8; 32],
+    pub config_digest: [u8; 32],
+    pub epoch_and_round: u128,
+    pub extra_hash: [u8; 32],
+    #[max_len(MAX_ORACLES)]
+    pub signers: Vec<[u8; 20]>,
+    pub expires_at: i64,
+    pub payer: Pubkey,
+}
+
+impl MerkleRoot {
+    pub fn initialize(
+        &mut self,
+        oracle_state: Pubkey,
+        root: [u8; 32],
+        config_digest: [u8; 32],
+        epoch_and_round: u128,
+        extra_hash: [u8; 32],
+        signers: Vec<[u8; 20]>,
+        expires_at: i64,
+        payer: Pubkey,
+    ) -> Result<()> {
+        self.oracle_state = oracle_state;
+        self.root = root;
+        self.config_digest = config_digest;
+        self.epoch_and_round = epoch_and_round;
+        self.extra_hash = extra_hash;
+        self.signers = signers;
+        self.expires_at = expires_at;
+        self.payer = payer;
+        Ok(())
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
@@ -1,5 +1,33 @@
+pub mod aggregate_feed;
+pub mod basket_feed;
+pub mod circuit_breaker;
+pub mod data_feed;
+pub mod derived_feed;
+pub mod feed_config;
+pub mod feed_registry;
+pub mod merkle_root;
+pub mod operator_registry;
 pub mod oracle_state;
+pub mod price_accumulator;
 pub mod price_feed;
+pub mod price_history;
+pub mod push_policy;
+pub mod rate_limit;
+pub mod signer_stats;
 
+pub use aggregate_feed::*;
+pub use basket_feed::*;
+pub use circuit_breaker::*;
+pub use data_feed::*;
+pub use derived_feed::*;
+pub use feed_config::*;
+pub use feed_registry::*;
+pub use merkle_root::*;
+pub use operator_registry::*;
 pub use oracle_state::*;
+pub use price_accumulator::*;
 pub use price_feed::*;
+pub use price_history::*;
+pub use push_policy::*;
+pub use rate_limit::*;
+pub use signer_stats::*;
@@ -0,0 +1,120 @@
+use crate::constants::{BPS_DENOMINATOR, MAX_ORACLES};
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct OperatorEntry {
+    pub oracle: [u8; 20],
+    pub payout: Pubkey,
+    pub accrued_rewards: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OperatorRegistry {
+    pub oracle_state: Pubkey,
+    pub operator_fee_bps: u64,
+    pub total_accrued: u64,
+    #[max_len(MAX_ORACLES)]
+    pub operators: Vec<OperatorEntry>,
+}
+
+impl OperatorRegistry {
+    pub fn initialize(&mut self, oracle_state: Pubkey, operator_fee_bps: u64) -> Result<()> {
+        self.oracle_state = oracle_state;
+        self.total_accrued = 0;
+        self.operators = Vec::new();
+        self.set_operator_fee_bps(operator_fee_bps)
+    }
+
+    pub fn set_operator_fee_bps(&mut self, operator_fee_bps: u64) -> Result<()> {
+        require!(
+            operator_fee_bps <= BPS_DENOMINATOR,
+            ErrorCode::InvalidOperatorFeeShare
+        );
+        self.operator_fee_bps = operator_fee_bps;
+        Ok(())
+    }
+
+    pub fn set_operator(&mut self, oracle: [u8; 20], payout: Pubkey) -> Result<()> {
+        match self
+            .operators
+            .iter_mut()
+            .find(|entry| entry.oracle == oracle)
+        {
+            Some(entry) => entry.payout = payout,
+            None => {
+                require!(
+                    self.operators.len() < MAX_ORACLES,
+                    ErrorCode::MaxOraclesReached
+                );
+                self.operators.push(OperatorEntry {
+                    oracle,
+                    payout,
+                    accrued_rewards: 0,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Splits the operator share of `fee` equally between the registered
+    /// operators among `signers` and credits each of them. Returns the oracle
+    /// addresses that were credited together with the total amount credited;
+    /// any remainder stays with the caller.
+    pub fn accrue(&mut self, signers: &[[u8; 20]], fee: u64) -> Result<(Vec<[u8; 20]>, u64)> {
+        let rewarded: Vec<[u8; 20]> = signers
+            .iter()
+            .filter(|signer| self.operators.iter().any(|entry| entry.oracle == **signer))
+            .copied()
+            .collect();
+
+        if rewarded.is_empty() {
+            return Ok((rewarded, 0));
+        }
+
+        let operator_share = fee
+            .checked_mul(self.operator_fee_bps)
+            .ok_or(ErrorCode::MathOverflow)?
+            / BPS_DENOMINATOR;
+        let per_operator = operator_share / rewarded.len() as u64;
+        if per_operator == 0 {
+            return Ok((Vec::new(), 0));
+        }
+
+        for entry in self
+            .operators
+            .iter_mut()
+            .filter(|entry| rewarded.contains(&entry.oracle))
+        {
+            entry.accrued_rewards = entry
+                .accrued_rewards
+                .checked_add(per_operator)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        let total = per_operator * rewarded.len() as u64;
+        self.total_accrued = self
+            .total_accrued
+            .checked_add(total)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok((rewarded, total))
+    }
+
+    /// Resets the accrued rewards of `oracle` and returns the amount owed.
+    pub fn take_rewards(&mut self, oracle: [u8; 20], payout: Pubkey) -> Result<u64> {
+        let entry = self
+            .operators
+            .iter_mut()
+            .find(|entry| entry.oracle == oracle)
+            .ok_or(ErrorCode::OperatorNotFound)?;
+        require_keys_eq!(entry.payout, payout, ErrorCode::UnauthorizedOperator);
+        require!(entry.accrued_rewards > 0, ErrorCode::NoRewardsToClaim);
+
+        let amount = entry.accrued_rewards;
+        entry.accrued_rewards = 0;
+        self.total_accrued -= amount;
+        Ok(amount)
+    }
+}
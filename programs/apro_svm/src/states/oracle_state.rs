@@ -1,7 +1,54 @@
+use crate::constants::MAX_ORACLES;
+use crate::errors::ErrorCode;
 use anchor_lang::prelude::*;
 
-#[account]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuorumMode {
+    /// Each oracle is one vote; `required_signatures` must sign.
+    Count,
+    /// Oracles vote with their weight; the signers' weights must add up to
+    /// `weight_threshold`.
+    Weight,
+}
+
+#[account(zero_copy)]
 pub struct OracleState {
+    pub id: u64,
+    pub required_signatures: u64,
+    pub total_oracles: u64,
+    pub admin: Pubkey,
+    pub expiration_period: i64,
+    /// How far, in bps of the benchmark, bid and ask may cross it.
+    pub price_ordering_tolerance_bps: u64,
+    pub weight_threshold: u64,
+    /// Weight of each entry of `oracles`, by index.
+    pub oracle_weights: [u64; MAX_ORACLES],
+    /// The first `total_oracles` entries are the oracle addresses.
+    pub oracles: [[u8; 20]; MAX_ORACLES],
+    /// Set once a `FeedRegistry` exists; new feeds must then be recorded in it.
+    pub feed_registry_enabled: u8,
+    /// When set, `update_price` only creates feeds that have a `FeedConfig`.
+    pub registered_feeds_only: u8,
+    /// A `QuorumMode`, see `quorum_mode()`.
+    pub quorum_mode: u8,
+    /// Set once `SignerStats` exist; verified reports must then be recorded
+    /// in them.
+    pub signer_stats_enabled: u8,
+    /// May set emergency fallback prices alongside the admin; unset when
+    /// default.
+    pub pauser: Pubkey,
+    /// Room for new fields. It also keeps the account larger than the legacy
+    /// Borsh layout, which is never read as zero-copy before migration.
+    pub _reserved: [u8; 316],
+}
+
+// `PriceFeed::signer_bitmap` has a bit per oracle.
+const _: () = assert!(MAX_ORACLES <= u16::BITS as usize);
+
+/// The Borsh layout of the original `OracleState` accounts, read by
+/// `migrate_oracle_state`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyOracleState {
     pub id: u64,
     pub required_signatures: u64,
     pub total_oracles: u64,
@@ -11,7 +58,9 @@ pub struct OracleState {
 }
 
 impl OracleState {
-    pub const LEN: usize = 8 + 4 + 32 + 64 + 32 + 64 + 16 + 16 + 16 + 32;
+    pub const LEN: usize = 8 + std::mem::size_of::<OracleState>();
+    /// Space `initialize_oracle` allocated for the legacy Borsh layout.
+    pub const LEGACY_LEN: usize = 8 + 8 + 8 + 8 + 32 + 32 * 20 + 8;
 
     pub fn initialize(
         &mut self,
@@ -25,12 +74,58 @@ impl OracleState {
         self.total_oracles = 0;
         self.admin = admin;
         self.expiration_period = expiration_period;
-        self.oracles = Vec::new();
         Ok(())
     }
 
+    /// Rebuilds the state from its legacy layout. Oracles weigh 1 and every
+    /// setting the legacy layout lacks starts at its default.
+    pub fn migrate(&mut self, legacy: &LegacyOracleState) -> Result<()> {
+        require!(
+            legacy.oracles.len() <= MAX_ORACLES,
+            ErrorCode::MaxOraclesReached
+        );
+
+        self.initialize(
+            legacy.id,
+            legacy.required_signatures,
+            legacy.admin,
+            legacy.expiration_period,
+        )?;
+        for oracle in &legacy.oracles {
+            self.add_oracle(*oracle)?;
+        }
+        Ok(())
+    }
+
+    /// The registered oracle addresses.
+    pub fn oracles(&self) -> &[[u8; 20]] {
+        &self.oracles[..self.total_oracles as usize]
+    }
+
+    pub fn feed_registry_enabled(&self) -> bool {
+        self.feed_registry_enabled != 0
+    }
+
+    pub fn registered_feeds_only(&self) -> bool {
+        self.registered_feeds_only != 0
+    }
+
+    pub fn signer_stats_enabled(&self) -> bool {
+        self.signer_stats_enabled != 0
+    }
+
+    pub fn quorum_mode(&self) -> QuorumMode {
+        match self.quorum_mode {
+            0 => QuorumMode::Count,
+            _ => QuorumMode::Weight,
+        }
+    }
+
     pub fn add_oracle(&mut self, oracle: [u8; 20]) -> Result<()> {
-        self.oracles.push(oracle);
+        let index = self.total_oracles as usize;
+        require!(index < MAX_ORACLES, ErrorCode::MaxOraclesReached);
+        self.oracles[index] = oracle;
+        self.oracle_weights[index] = 1;
         self.total_oracles += 1;
         Ok(())
     }
@@ -39,4 +134,99 @@ impl OracleState {
         self.admin = new_admin;
         Ok(())
     }
+
+    /// Whether `key` is the admin or, when one is set, the pauser.
+    pub fn is_admin_or_pauser(&self, key: &Pubkey) -> bool {
+        *key == self.admin || (self.pauser != Pubkey::default() && *key == self.pauser)
+    }
+
+    fn position(&self, oracle: &[u8; 20]) -> Option<usize> {
+        self.oracles().iter().position(|entry| entry == oracle)
+    }
+
+    /// Bitmap of `signers` by their index in `oracles`; see
+    /// `PriceFeed::signer_bitmap`.
+    pub fn signer_bitmap(&self, signers: &[[u8; 20]]) -> u16 {
+        signers
+            .iter()
+            .filter_map(|signer| self.position(signer))
+            .fold(0, |bitmap, index| bitmap | 1 << index)
+    }
+
+    pub fn weight_of(&self, oracle: &[u8; 20]) -> u64 {
+        self.position(oracle)
+            .map_or(0, |index| self.oracle_weights[index])
+    }
+
+    /// Summed weight of `oracles`.
+    pub fn weight_of_all(&self, oracles: &[[u8; 20]]) -> u64 {
+        oracles.iter().fold(0u64, |weight, oracle| {
+            weight.saturating_add(self.weight_of(oracle))
+        })
+    }
+
+    /// What `signers` count for towards a quorum: their number, or their
+    /// summed weight in weight mode.
+    pub fn voting_power(&self, signers: &[[u8; 20]]) -> u64 {
+        match self.quorum_mode() {
+            QuorumMode::Count => signers.len() as u64,
+            QuorumMode::Weight => self.weight_of_all(signers),
+        }
+    }
+
+    pub fn add_weighted_oracle(&mut self, oracle: [u8; 20], weight: u64) -> Result<()> {
+        self.add_oracle(oracle)?;
+        self.set_oracle_weight(oracle, weight)
+    }
+
+    pub fn set_oracle_weight(&mut self, oracle: [u8; 20], weight: u64) -> Result<()> {
+        require!(weight > 0, ErrorCode::InvalidOracleWeight);
+        let index = self.position(&oracle).ok_or(ErrorCode::OracleNotFound)?;
+        self.oracle_weights[index] = weight;
+        self.require_reachable_weight_threshold()
+    }
+
+    /// Replaces `old_oracle` with `new_oracle` in place, giving it `weight`.
+    pub fn rotate_oracle(
+        &mut self,
+        old_oracle: [u8; 20],
+        new_oracle: [u8; 20],
+        weight: u64,
+    ) -> Result<()> {
+        require!(
+            !self.oracles().contains(&new_oracle),
+            ErrorCode::OracleAlreadyExists
+        );
+        let index = self
+            .position(&old_oracle)
+            .ok_or(ErrorCode::OracleNotFound)?;
+        self.oracles[index] = new_oracle;
+        self.set_oracle_weight(new_oracle, weight)
+    }
+
+    pub fn set_quorum_mode(
+        &mut self,
+        quorum_mode: QuorumMode,
+        weight_threshold: u64,
+    ) -> Result<()> {
+        require!(
+            quorum_mode == QuorumMode::Count || weight_threshold > 0,
+            ErrorCode::InvalidWeightThreshold
+        );
+        self.quorum_mode = quorum_mode as u8;
+        self.weight_threshold = weight_threshold;
+        self.require_reachable_weight_threshold()
+    }
+
+    /// Fails in weight mode if all oracles together cannot reach the
+    /// `weight_threshold`. Checked whenever the threshold or a weight
+    /// changes; adding an oracle only adds weight.
+    fn require_reachable_weight_threshold(&self) -> Result<()> {
+        require!(
+            self.quorum_mode() == QuorumMode::Count
+                || self.weight_threshold <= self.weight_of_all(self.oracles()),
+            ErrorCode::UnreachableWeightThreshold
+        );
+        Ok(())
+    }
 }
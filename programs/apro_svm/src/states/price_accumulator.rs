@@ -0,0 +1,83 @@
+use crate::constants::ACCUMULATOR_OBSERVATIONS;
+use anchor_lang::prelude::*;
+
+/// Snapshot taken whenever the accumulator advances: the cumulative value at
+/// `timestamp` and the price that starts accruing from that point on.
+#[zero_copy]
+pub struct PriceObservation {
+    pub timestamp: u128,
+    pub cumulative_price: u128,
+    pub price: u128,
+}
+
+/// Time-weighted accumulators for a feed, advanced by `update_price`.
+///
+/// `cumulative_price` is the running sum of `benchmark_price * seconds` and
+/// wraps on overflow, so only differences between two snapshots are
+/// meaningful. `ema_price` is a time-weighted exponential moving average in
+/// the same units as `benchmark_price`.
+#[account(zero_copy)]
+pub struct PriceAccumulator {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub last_timestamp: u128,
+    pub last_price: u128,
+    pub cumulative_price: u128,
+    pub ema_price: u128,
+    pub ema_period: u64,
+    pub observation_head: u32,
+    pub observation_len: u32,
+    pub observations: [PriceObservation; ACCUMULATOR_OBSERVATIONS],
+}
+
+impl PriceAccumulator {
+    pub const LEN: usize = 8 + std::mem::size_of::<PriceAccumulator>();
+
+    pub fn initialize(&mut self, oracle_state: Pubkey, feed_id: [u8; 32], ema_period: u64) {
+        self.oracle_state = oracle_state;
+        self.feed_id = feed_id;
+        self.ema_period = ema_period;
+    }
+
+    /// Accrues the previous price up to `timestamp` and starts accruing
+    /// `price`. `apply_report` only passes reports newer than the feed's;
+    /// any other report is ignored.
+    pub fn record(&mut self, timestamp: u128, price: u128) {
+        if self.observation_len == 0 {
+            self.ema_price = price;
+        } else if timestamp > self.last_timestamp {
+            let elapsed = timestamp - self.last_timestamp;
+            self.cumulative_price = self
+                .cumulative_price
+                .wrapping_add(self.last_price.wrapping_mul(elapsed));
+
+            // First order approximation of alpha = 1 - exp(-elapsed / period),
+            // saturating at 1 once a full period has passed.
+            let period = u128::from(self.ema_period.max(1));
+            let weight = elapsed.min(period);
+            self.ema_price = if price >= self.ema_price {
+                self.ema_price + scale(price - self.ema_price, weight, period)
+            } else {
+                self.ema_price - scale(self.ema_price - price, weight, period)
+            };
+        } else {
+            return;
+        }
+
+        self.last_timestamp = timestamp;
+        self.last_price = price;
+        self.observations[self.observation_head as usize] = PriceObservation {
+            timestamp,
+            cumulative_price: self.cumulative_price,
+            price,
+        };
+        self.observation_head = (self.observation_head + 1) % ACCUMULATOR_OBSERVATIONS as u32;
+        self.observation_len = (self.observation_len + 1).min(ACCUMULATOR_OBSERVATIONS as u32);
+    }
+}
+
+/// Computes `value * numerator / denominator` for `numerator <= denominator`
+/// without overflowing the intermediate product.
+fn scale(value: u128, numerator: u128, denominator: u128) -> u128 {
+    value / denominator * numerator + value % denominator * numerator / denominator
+}
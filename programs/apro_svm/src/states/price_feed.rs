@@ -1,7 +1,71 @@
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::ErrorCode;
+use crate::states::{BreakerAction, BreakerState, CircuitBreaker, UpdateReason};
 use anchor_lang::prelude::*;
 
-#[account]
+/// Where the stored price comes from.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceSource {
+    /// A report signed by the DON.
+    Oracle,
+    /// A fallback price set by the admin or pauser, valid until `expire_at`.
+    Manual,
+}
+
+/// A feed's latest price report. Report timestamps are narrowed to `u64`
+/// when stored; fields are ordered so the layout has no padding and its
+/// size is `INIT_SPACE`.
+#[account(zero_copy)]
+#[derive(InitSpace)]
 pub struct PriceFeed {
+    pub feed_id: [u8; 32],
+    pub native_fee: u128,
+    pub apro_token_fee: u128,
+    pub benchmark_price: u128,
+    pub ask_price: u128,
+    pub bid_price: u128,
+    pub epoch_and_round: u128,
+    /// Half the bid/ask spread, in price units.
+    pub confidence: u128,
+    pub config_digest: [u8; 32],
+    pub extra_hash: [u8; 32],
+    pub valid_time_stamp: u64,
+    pub observe_time_stamp: u64,
+    pub expire_at: u64,
+    /// Bid/ask spread relative to `benchmark_price`, in basis points.
+    pub spread_bps: u64,
+    /// The feed's `CircuitBreaker`, see `circuit_breaker()`.
+    pub breaker_max_deviation_bps: u64,
+    pub breaker_max_rate_bps_per_sec: u64,
+    pub breaker_confirmation_signatures: u64,
+    pub breaker_action: u8,
+    /// A `BreakerState`, see `breaker_state()`.
+    pub breaker_state: u8,
+    /// Bit `i` is set if the `OracleState`'s oracle at index `i` signed the
+    /// stored report.
+    pub signer_bitmap: u16,
+    pub _padding: [u8; 4],
+    /// Slot and cluster time at which the stored report was applied.
+    pub last_update_slot: u64,
+    pub last_update_unix_ts: i64,
+    /// Number of reports applied to the feed.
+    pub update_count: u64,
+    /// Payer of the transaction that applied the stored report.
+    pub updater: Pubkey,
+    /// An `UpdateReason`, see `update_reason()`.
+    pub update_reason: u8,
+    /// A `PriceSource`, see `price_source()`.
+    pub price_source: u8,
+    /// Room for new fields.
+    pub _reserved: [u8; 54],
+}
+
+const _: () = assert!(PriceFeed::INIT_SPACE == std::mem::size_of::<PriceFeed>());
+
+/// The Borsh layout of the original `PriceFeed` accounts, read by
+/// `migrate_price_feed`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyPriceFeed {
     pub feed_id: [u8; 32],
     pub valid_time_stamp: u128,
     pub observe_time_stamp: u128,
@@ -16,8 +80,62 @@ pub struct PriceFeed {
     pub extra_hash: [u8; 32],
 }
 
+impl LegacyPriceFeed {
+    /// Space `update_price` allocated for legacy accounts.
+    pub const LEN: usize = 8 + LegacyPriceFeed::INIT_SPACE;
+}
+
 impl PriceFeed {
-    pub const LEN: usize = 8 + 4 + 32 + 64 + 32 + 64 + 16 + 16 + 16 + 32;
+    pub const LEN: usize = 8 + PriceFeed::INIT_SPACE;
+
+    /// Rebuilds the feed from its legacy Borsh layout. Fields the legacy
+    /// layout lacks start out as on a new feed.
+    pub fn migrate(&mut self, legacy: &LegacyPriceFeed) -> Result<()> {
+        self.feed_id = legacy.feed_id;
+        self.valid_time_stamp = narrow(legacy.valid_time_stamp)?;
+        self.observe_time_stamp = narrow(legacy.observe_time_stamp)?;
+        self.native_fee = legacy.native_fee;
+        self.apro_token_fee = legacy.apro_token_fee;
+        self.expire_at = narrow(legacy.expire_at)?;
+        self.benchmark_price = legacy.benchmark_price;
+        self.ask_price = legacy.ask_price;
+        self.bid_price = legacy.bid_price;
+        self.config_digest = legacy.config_digest;
+        self.epoch_and_round = legacy.epoch_and_round;
+        self.extra_hash = legacy.extra_hash;
+        Ok(())
+    }
+
+    pub fn circuit_breaker(&self) -> CircuitBreaker {
+        CircuitBreaker {
+            max_deviation_bps: self.breaker_max_deviation_bps,
+            max_rate_bps_per_sec: self.breaker_max_rate_bps_per_sec,
+            action: match self.breaker_action {
+                0 => BreakerAction::Reject,
+                _ => BreakerAction::RequireConfirmation,
+            },
+            confirmation_signatures: self.breaker_confirmation_signatures,
+        }
+    }
+
+    pub fn set_circuit_breaker(&mut self, circuit_breaker: CircuitBreaker) {
+        self.breaker_max_deviation_bps = circuit_breaker.max_deviation_bps;
+        self.breaker_max_rate_bps_per_sec = circuit_breaker.max_rate_bps_per_sec;
+        self.breaker_action = circuit_breaker.action as u8;
+        self.breaker_confirmation_signatures = circuit_breaker.confirmation_signatures;
+    }
+
+    pub fn breaker_state(&self) -> BreakerState {
+        match self.breaker_state {
+            0 => BreakerState::Normal,
+            1 => BreakerState::NeedsConfirmation,
+            _ => BreakerState::Acknowledged,
+        }
+    }
+
+    pub fn set_breaker_state(&mut self, breaker_state: BreakerState) {
+        self.breaker_state = breaker_state as u8;
+    }
 
     pub fn update_price(
         &mut self,
@@ -35,17 +153,147 @@ impl PriceFeed {
         extra_hash: [u8; 32],
     ) -> Result<()> {
         self.feed_id = feed_id;
-        self.valid_time_stamp = valid_time_stamp;
-        self.observe_time_stamp = observe_time_stamp;
+        self.valid_time_stamp = narrow(valid_time_stamp)?;
+        self.observe_time_stamp = narrow(observe_time_stamp)?;
         self.native_fee = native_fee;
         self.apro_token_fee = apro_token_fee;
-        self.expire_at = expire_at;
+        self.expire_at = narrow(expire_at)?;
         self.benchmark_price = benchmark_price;
         self.ask_price = ask_price;
         self.bid_price = bid_price;
         self.config_digest = config_digest;
         self.epoch_and_round = epoch_and_round;
         self.extra_hash = extra_hash;
+
+        let spread = ask_price.saturating_sub(bid_price);
+        self.confidence = spread / 2;
+        self.spread_bps = match benchmark_price {
+            0 if spread > 0 => u64::MAX,
+            0 => 0,
+            _ => u64::try_from(spread.saturating_mul(BPS_DENOMINATOR as u128) / benchmark_price)
+                .unwrap_or(u64::MAX),
+        };
+        Ok(())
+    }
+
+    pub fn update_reason(&self) -> UpdateReason {
+        match self.update_reason {
+            0 => UpdateReason::Unscheduled,
+            1 => UpdateReason::Heartbeat,
+            _ => UpdateReason::Deviation,
+        }
+    }
+
+    pub fn price_source(&self) -> PriceSource {
+        match self.price_source {
+            0 => PriceSource::Oracle,
+            _ => PriceSource::Manual,
+        }
+    }
+
+    /// Stores a `Manual` price set by `admin` at `now`, valid until
+    /// `expire_at`. The next signed report newer than `now` replaces it.
+    pub fn set_fallback_price(
+        &mut self,
+        price: u128,
+        now: i64,
+        expire_at: i64,
+        admin: Pubkey,
+    ) -> Result<()> {
+        let now = u64::try_from(now).map_err(|_| error!(ErrorCode::ValueOutOfRange))?;
+        self.benchmark_price = price;
+        self.ask_price = price;
+        self.bid_price = price;
+        self.confidence = 0;
+        self.spread_bps = 0;
+        self.valid_time_stamp = now;
+        self.observe_time_stamp = now;
+        self.expire_at =
+            u64::try_from(expire_at).map_err(|_| error!(ErrorCode::ValueOutOfRange))?;
+        self.price_source = PriceSource::Manual as u8;
+        self.record_update(admin, 0, UpdateReason::Unscheduled)
+    }
+
+    /// Records when, by whom and why the report just stored was applied.
+    pub fn record_update(
+        &mut self,
+        updater: Pubkey,
+        signer_bitmap: u16,
+        reason: UpdateReason,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        self.update_reason = reason as u8;
+        self.signer_bitmap = signer_bitmap;
+        self.last_update_slot = clock.slot;
+        self.last_update_unix_ts = clock.unix_timestamp;
+        self.update_count = self.update_count.saturating_add(1);
+        self.updater = updater;
         Ok(())
     }
+
+    /// Runs the circuit breaker on a report whose signers have
+    /// `voting_power` (see `OracleState::voting_power`). Returns the
+    /// deviation in bps from the current price if the report must be held
+    /// back instead of applied, tripping the breaker. A report within the
+    /// limits, a confirmed one or the first one after an acknowledgement
+    /// resets it.
+    pub fn check_circuit_breaker(
+        &mut self,
+        benchmark_price: u128,
+        valid_time_stamp: u128,
+        voting_power: u64,
+    ) -> Option<u64> {
+        let breaker = self.circuit_breaker();
+        let confirmed = breaker.action == BreakerAction::RequireConfirmation
+            && voting_power >= breaker.confirmation_signatures;
+        let deviation_bps = breaker.exceeded(
+            self.benchmark_price,
+            benchmark_price,
+            valid_time_stamp.saturating_sub(u128::from(self.valid_time_stamp)),
+        );
+
+        match deviation_bps {
+            Some(deviation_bps)
+                if !confirmed && self.breaker_state() != BreakerState::Acknowledged =>
+            {
+                self.set_breaker_state(BreakerState::NeedsConfirmation);
+                Some(deviation_bps)
+            }
+            _ => {
+                self.set_breaker_state(BreakerState::Normal);
+                None
+            }
+        }
+    }
+
+    /// Checks `bid_price <= benchmark_price <= ask_price`, allowing each
+    /// side to cross by `tolerance_bps` of the benchmark. Reports without
+    /// quotes (zero bid and ask) are not checked.
+    pub fn validate_ordering(
+        benchmark_price: u128,
+        ask_price: u128,
+        bid_price: u128,
+        tolerance_bps: u64,
+    ) -> Result<()> {
+        if bid_price == 0 && ask_price == 0 {
+            return Ok(());
+        }
+
+        let tolerance = u128::from(tolerance_bps);
+        let denominator = u128::from(BPS_DENOMINATOR);
+        let slack = benchmark_price / denominator * tolerance
+            + benchmark_price % denominator * tolerance / denominator;
+
+        require!(
+            bid_price <= benchmark_price.saturating_add(slack)
+                && ask_price.saturating_add(slack) >= benchmark_price,
+            ErrorCode::InvalidPriceOrdering
+        );
+        Ok(())
+    }
+}
+
+/// Narrows a report timestamp to the stored width.
+fn narrow(time_stamp: u128) -> Result<u64> {
+    u64::try_from(time_stamp).map_err(|_| error!(ErrorCode::ValueOutOfRange))
 }
@@ -0,0 +1,67 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[zero_copy]
+pub struct PriceHistoryEntry {
+    pub valid_time_stamp: u128,
+    pub benchmark_price: u128,
+    pub bid_price: u128,
+    pub ask_price: u128,
+    pub epoch_and_round: u128,
+}
+
+/// Header of a per-feed ring buffer of recent reports. The account holds
+/// `capacity` entries directly after the header; `head` is the slot the next
+/// entry is written to and `len` stops growing once the buffer is full.
+#[account(zero_copy)]
+pub struct PriceHistory {
+    pub oracle_state: Pubkey,
+    pub feed_id: [u8; 32],
+    pub capacity: u64,
+    pub len: u64,
+    pub head: u64,
+}
+
+impl PriceHistory {
+    pub const ENTRIES_OFFSET: usize = 8 + size_of::<PriceHistory>();
+
+    pub fn space(capacity: u64) -> usize {
+        Self::ENTRIES_OFFSET + capacity as usize * size_of::<PriceHistoryEntry>()
+    }
+
+    pub fn initialize(&mut self, oracle_state: Pubkey, feed_id: [u8; 32], capacity: u64) {
+        self.oracle_state = oracle_state;
+        self.feed_id = feed_id;
+        self.capacity = capacity;
+        self.len = 0;
+        self.head = 0;
+    }
+
+    /// Writes `entry` into the ring buffer stored in `account`, overwriting
+    /// the oldest entry once the buffer is full. Entries are kept in time
+    /// order: `entry` must be newer than the latest one.
+    pub fn push(account: &AccountInfo, entry: PriceHistoryEntry) -> Result<()> {
+        let mut data = account.try_borrow_mut_data()?;
+        let (header, entries) = data[8..].split_at_mut(size_of::<PriceHistory>());
+        let history: &mut PriceHistory = bytemuck::from_bytes_mut(header);
+        let entries: &mut [PriceHistoryEntry] = bytemuck::try_cast_slice_mut(
+            &mut entries[..history.capacity as usize * size_of::<PriceHistoryEntry>()],
+        )
+        .map_err(|_| error::ErrorCode::AccountDidNotDeserialize)?;
+
+        if history.len > 0 {
+            let latest =
+                &entries[((history.head + history.capacity - 1) % history.capacity) as usize];
+            require!(
+                entry.valid_time_stamp > latest.valid_time_stamp,
+                ErrorCode::StaleReport
+            );
+        }
+        entries[history.head as usize] = entry;
+        history.head = (history.head + 1) % history.capacity;
+        history.len = (history.len + 1).min(history.capacity);
+
+        Ok(())
+    }
+}
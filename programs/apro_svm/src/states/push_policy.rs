@@ -0,0 +1,64 @@
+use crate::constants::BPS_DENOMINATOR;
+use anchor_lang::prelude::*;
+
+/// Why a report was pushed, as judged by the feed's `PushPolicy`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum UpdateReason {
+    /// Neither the heartbeat nor the deviation threshold was due.
+    Unscheduled,
+    Heartbeat,
+    Deviation,
+}
+
+/// The service level a feed is published at: a report at least every
+/// `heartbeat_seconds`, and whenever the price moves `deviation_bps` from
+/// the stored one. A zero value disables that trigger.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct PushPolicy {
+    pub heartbeat_seconds: u64,
+    pub deviation_bps: u64,
+    /// Reject `Unscheduled` reports instead of storing them.
+    pub reject_unscheduled: bool,
+}
+
+impl PushPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.heartbeat_seconds > 0 || self.deviation_bps > 0
+    }
+
+    /// Classifies a report at `time_stamp` moving the feed from
+    /// `previous_price`, stored at `previous_time_stamp`, to `price`. The
+    /// first report of a feed is a heartbeat.
+    pub fn reason(
+        &self,
+        previous_price: u128,
+        previous_time_stamp: u128,
+        price: u128,
+        time_stamp: u128,
+    ) -> UpdateReason {
+        if previous_time_stamp == 0 {
+            return UpdateReason::Heartbeat;
+        }
+
+        let deviation_bps = match previous_price {
+            0 => u128::MAX,
+            _ => {
+                previous_price
+                    .abs_diff(price)
+                    .saturating_mul(BPS_DENOMINATOR as u128)
+                    / previous_price
+            }
+        };
+        if self.deviation_bps > 0 && deviation_bps >= u128::from(self.deviation_bps) {
+            UpdateReason::Deviation
+        } else if self.heartbeat_seconds > 0
+            && time_stamp.saturating_sub(previous_time_stamp) >= u128::from(self.heartbeat_seconds)
+        {
+            UpdateReason::Heartbeat
+        } else {
+            UpdateReason::Unscheduled
+        }
+    }
+}
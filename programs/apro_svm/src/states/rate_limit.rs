@@ -0,0 +1,42 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Minimum spacing between accepted updates of a feed, in slots since the
+/// last update landed and in seconds between report timestamps. A zero
+/// value disables that limit.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct RateLimit {
+    pub min_interval_slots: u64,
+    pub min_interval_seconds: u64,
+    /// Unix time until which the admin lifted the limit.
+    pub override_until: i64,
+}
+
+impl RateLimit {
+    /// Fails if a report at `time_stamp`, landing in `slot` at `now`, comes
+    /// too soon after the previous update, made in `last_update_slot` with a
+    /// report at `last_time_stamp`. The first update of a feed is always
+    /// accepted.
+    pub fn check(
+        &self,
+        last_update_slot: u64,
+        last_time_stamp: u128,
+        slot: u64,
+        time_stamp: u128,
+        now: i64,
+    ) -> Result<()> {
+        if last_time_stamp == 0 || now < self.override_until {
+            return Ok(());
+        }
+
+        require!(
+            slot.saturating_sub(last_update_slot) >= self.min_interval_slots
+                && time_stamp.saturating_sub(last_time_stamp)
+                    >= u128::from(self.min_interval_seconds),
+            ErrorCode::UpdateTooFrequent
+        );
+        Ok(())
+    }
+}
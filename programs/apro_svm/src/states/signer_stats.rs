@@ -0,0 +1,68 @@
+use crate::constants::MAX_ORACLES;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SignerActivity {
+    pub oracle: [u8; 20],
+    /// Slot of the last report the oracle validly signed, or 0 if none.
+    pub last_signed_slot: u64,
+    /// One bit per report recorded since the oracle was registered, the
+    /// most recent in bit 0; a set bit means the oracle signed it.
+    pub participation: u64,
+}
+
+impl SignerActivity {
+    /// How many of the last 64 reports the oracle signed.
+    pub fn participation_count(&self) -> u32 {
+        self.participation.count_ones()
+    }
+}
+
+/// Which of an `OracleState`'s oracles sign the reports verified under it.
+#[account]
+#[derive(InitSpace)]
+pub struct SignerStats {
+    pub oracle_state: Pubkey,
+    /// Number of reports recorded.
+    pub report_count: u64,
+    #[max_len(MAX_ORACLES)]
+    pub signers: Vec<SignerActivity>,
+}
+
+impl SignerStats {
+    pub fn initialize(&mut self, oracle_state: Pubkey) {
+        self.oracle_state = oracle_state;
+        self.report_count = 0;
+        self.signers = Vec::new();
+    }
+
+    /// Records a verified report signed by `signers` among the `OracleState`'s
+    /// current `oracles`. Oracles no longer registered are dropped and new
+    /// ones start with an empty history.
+    pub fn record(&mut self, oracles: &[[u8; 20]], signers: &[[u8; 20]], slot: u64) {
+        self.signers
+            .retain(|activity| oracles.contains(&activity.oracle));
+        for oracle in oracles {
+            if !self
+                .signers
+                .iter()
+                .any(|activity| activity.oracle == *oracle)
+            {
+                self.signers.push(SignerActivity {
+                    oracle: *oracle,
+                    last_signed_slot: 0,
+                    participation: 0,
+                });
+            }
+        }
+
+        for activity in self.signers.iter_mut() {
+            let signed = signers.contains(&activity.oracle);
+            activity.participation = activity.participation << 1 | u64::from(signed);
+            if signed {
+                activity.last_signed_slot = slot;
+            }
+        }
+        self.report_count = self.report_count.saturating_add(1);
+    }
+}
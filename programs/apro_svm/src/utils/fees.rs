@@ -0,0 +1,71 @@
+use crate::constants::UPDATE_FEE_LAMPORTS;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+#[event]
+pub struct OperatorRewardsAccrued {
+    pub feed_id: [u8; 32],
+    pub operators: Vec<[u8; 20]>,
+    pub amount_per_operator: u64,
+}
+
+/// Credits the operator share of one update fee to the registered operators
+/// among `verified_oracles` and returns the amount credited.
+pub fn accrue_operator_rewards(
+    operator_registry: Option<&mut Account<OperatorRegistry>>,
+    feed_id: [u8; 32],
+    verified_oracles: &[[u8; 20]],
+) -> Result<u64> {
+    let Some(operator_registry) = operator_registry else {
+        return Ok(0);
+    };
+
+    let (operators, operator_amount) =
+        operator_registry.accrue(verified_oracles, UPDATE_FEE_LAMPORTS)?;
+
+    if operator_amount > 0 {
+        emit!(OperatorRewardsAccrued {
+            feed_id,
+            amount_per_operator: operator_amount / operators.len() as u64,
+            operators,
+        });
+    }
+
+    Ok(operator_amount)
+}
+
+/// Charges `total_fee` to the payer. `operator_amount` of it goes to the
+/// operator registry and the rest to the admin.
+pub fn transfer_fees<'info>(
+    payer: &Signer<'info>,
+    admin: &SystemAccount<'info>,
+    operator_registry: Option<&Account<'info, OperatorRegistry>>,
+    system_program: &Program<'info, System>,
+    total_fee: u64,
+    operator_amount: u64,
+) -> Result<()> {
+    if let Some(operator_registry) = operator_registry.filter(|_| operator_amount > 0) {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: operator_registry.to_account_info(),
+                },
+            ),
+            operator_amount,
+        )?;
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: admin.to_account_info(),
+            },
+        ),
+        total_fee - operator_amount,
+    )
+}
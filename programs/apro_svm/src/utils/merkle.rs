@@ -0,0 +1,18 @@
+use anchor_lang::solana_program::keccak::hashv;
+
+/// Hashes a pair of nodes in sorted order, so proofs need no direction bits.
+/// Matches OpenZeppelin's `MerkleProof` on the EVM side.
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+pub fn verify_proof(leaf: &[u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(*leaf, |node, sibling| hash_pair(&node, sibling));
+    computed == *root
+}
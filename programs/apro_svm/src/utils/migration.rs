@@ -0,0 +1,86 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::ZeroCopy;
+use std::cell::Ref;
+
+/// Checks `account` is one of this program's accounts of type `T` in its
+/// legacy layout, allocated `legacy_len` bytes, and returns its data after
+/// the discriminator.
+pub fn read_legacy<T: ZeroCopy + Owner>(
+    account: &AccountInfo,
+    legacy_len: usize,
+) -> Result<Vec<u8>> {
+    require_keys_eq!(*account.owner, T::owner(), ErrorCode::InvalidLegacyAccount);
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() == legacy_len && data[..8] == T::discriminator(),
+        ErrorCode::InvalidLegacyAccount
+    );
+
+    Ok(data[8..].to_vec())
+}
+
+/// Fails unless `account` has the zero-copy layout of `T`. Loading an
+/// account still in an older layout would read past its data.
+pub fn require_migrated<T: ZeroCopy>(account: &AccountInfo) -> Result<()> {
+    require!(
+        account.data_len() == 8 + std::mem::size_of::<T>(),
+        ErrorCode::AccountNotMigrated
+    );
+    Ok(())
+}
+
+/// Loading of zero-copy accounts that may still be in an older layout.
+/// Account constraints use it before anything else reads the account, as
+/// `AccountLoader::load` panics on a shorter legacy account.
+pub trait LoadMigrated<T> {
+    /// Whether the account has the zero-copy layout of `T`.
+    fn is_migrated(&self) -> bool;
+
+    /// Loads the account, failing with `AccountNotMigrated` if it is still
+    /// in an older layout.
+    fn load_migrated(&self) -> Result<Ref<'_, T>>;
+}
+
+impl<'info, T: ZeroCopy + Owner> LoadMigrated<T> for AccountLoader<'info, T> {
+    fn is_migrated(&self) -> bool {
+        require_migrated::<T>(self.as_ref()).is_ok()
+    }
+
+    fn load_migrated(&self) -> Result<Ref<'_, T>> {
+        require_migrated::<T>(self.as_ref())?;
+        self.load()
+    }
+}
+
+/// Resizes `account` to the zero-copy layout of `T`, topping its rent up
+/// from `payer`, and hands `write` the zeroed account to fill in.
+pub fn write_zero_copy<'info, T: ZeroCopy>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    write: impl FnOnce(&mut T) -> Result<()>,
+) -> Result<()> {
+    let len = 8 + std::mem::size_of::<T>();
+    let rent = Rent::get()?.minimum_balance(len);
+    let top_up = rent.saturating_sub(account.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.realloc(len, true)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+    data[..8].copy_from_slice(&T::discriminator());
+    write(bytemuck::from_bytes_mut(&mut data[8..len]))
+}
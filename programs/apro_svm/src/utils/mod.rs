@@ -0,0 +1,11 @@
+pub mod fees;
+pub mod merkle;
+pub mod migration;
+pub mod report;
+pub mod source;
+
+pub use fees::*;
+pub use merkle::*;
+pub use migration::*;
+pub use report::*;
+pub use source::*;
@@ -0,0 +1,398 @@
+use crate::errors::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    keccak::hash as keccak_hash, msg, secp256k1_recover::secp256k1_recover,
+};
+use ethabi::{encode, Token, Uint};
+
+/// A signed DON price report as accepted by `update_price` and
+/// `update_prices`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PriceReport {
+    pub feed_id: [u8; 32],
+    pub valid_time_stamp: u128,
+    pub observe_time_stamp: u128,
+    pub native_fee: u128,
+    pub apro_token_fee: u128,
+    pub expire_at: u128,
+    pub benchmark_price: u128,
+    pub ask_price: u128,
+    pub bid_price: u128,
+    pub config_digest: [u8; 32],
+    pub epoch_and_round: u128,
+    pub extra_hash: [u8; 32],
+    pub signatures: Vec<[u8; 64]>,
+    pub recovery_ids: Vec<u8>,
+}
+
+impl PriceReport {
+    /// `keccak256(abi.encode(report_data))`. This is also the leaf of the
+    /// report in a Merkle-batched submission.
+    pub fn report_data_hash(&self) -> [u8; 32] {
+        let report_data = vec![
+            Token::FixedBytes(self.feed_id.to_vec()),
+            Token::Uint(self.valid_time_stamp.into()),
+            Token::Uint(self.observe_time_stamp.into()),
+            Token::Uint(self.native_fee.into()),
+            Token::Uint(self.apro_token_fee.into()),
+            Token::Uint(self.expire_at.into()),
+            Token::Uint(self.benchmark_price.into()),
+            Token::Uint(self.ask_price.into()),
+            Token::Uint(self.bid_price.into()),
+        ];
+
+        let encoded_report_data = encode(&report_data);
+        let report_data_hash = keccak_hash(&encoded_report_data);
+        msg!("Report Data Hash: 0x{}", hex::encode(report_data_hash));
+
+        report_data_hash.to_bytes()
+    }
+
+    /// Checks the report carries `quorum` and returns the distinct oracle
+    /// addresses that signed it.
+    pub fn verify(&self, oracle_state: &OracleState, quorum: &Quorum) -> Result<Vec<[u8; 20]>> {
+        let h = signing_hash(
+            &self.report_data_hash(),
+            &self.config_digest,
+            self.epoch_and_round,
+            &self.extra_hash,
+        );
+
+        verify_quorum(
+            oracle_state,
+            quorum,
+            &h,
+            &self.signatures,
+            &self.recovery_ids,
+        )
+    }
+
+    /// Checks the report carries both the primary and the secondary quorum.
+    /// An oracle in both sets counts only for the primary. Returns the
+    /// primary's counted oracles.
+    pub fn verify_dual(
+        &self,
+        primary: (&OracleState, &Quorum),
+        secondary: (&OracleState, &Quorum),
+    ) -> Result<Vec<[u8; 20]>> {
+        require!(
+            self.signatures.len() == self.recovery_ids.len(),
+            ErrorCode::ArrayLengthMismatch
+        );
+        let h = signing_hash(
+            &self.report_data_hash(),
+            &self.config_digest,
+            self.epoch_and_round,
+            &self.extra_hash,
+        );
+
+        let (oracle_state, quorum) = primary;
+        let primary_oracles = verify_signatures(
+            oracle_state,
+            quorum,
+            &h,
+            &self.signatures,
+            &self.recovery_ids,
+        )?;
+        if let Err(error) = quorum.check_met(oracle_state, &primary_oracles) {
+            msg!("Primary quorum: {}", error);
+            return err!(ErrorCode::PrimaryQuorumNotMet);
+        }
+
+        let (oracle_state, quorum) = secondary;
+        let secondary_oracles: Vec<[u8; 20]> = verify_signatures(
+            oracle_state,
+            quorum,
+            &h,
+            &self.signatures,
+            &self.recovery_ids,
+        )?
+        .into_iter()
+        .filter(|oracle| !primary_oracles.contains(oracle))
+        .collect();
+        if let Err(error) = quorum.check_met(oracle_state, &secondary_oracles) {
+            msg!("Secondary quorum: {}", error);
+            return err!(ErrorCode::SecondaryQuorumNotMet);
+        }
+
+        Ok(primary_oracles)
+    }
+
+    pub fn store(&self, price_feed: &mut PriceFeed) -> Result<()> {
+        price_feed.update_price(
+            self.feed_id,
+            self.valid_time_stamp,
+            self.observe_time_stamp,
+            self.native_fee,
+            self.apro_token_fee,
+            self.expire_at,
+            self.benchmark_price,
+            self.ask_price,
+            self.bid_price,
+            self.config_digest,
+            self.epoch_and_round,
+            self.extra_hash,
+        )
+    }
+}
+
+/// A report value with its EVM type, so the report hashes exactly as the
+/// DON encoded it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum DataValue {
+    /// `int192`
+    Int(i128),
+    /// `uint192`
+    Uint(u128),
+}
+
+impl DataValue {
+    fn token(&self) -> Token {
+        match *self {
+            // abi.encode sign-extends signed values to 256 bits.
+            DataValue::Int(value) if value < 0 => Token::Int(!Uint::from(!value as u128)),
+            DataValue::Int(value) => Token::Int(Uint::from(value as u128)),
+            DataValue::Uint(value) => Token::Uint(value.into()),
+        }
+    }
+
+    pub fn to_i128(&self) -> Result<i128> {
+        match *self {
+            DataValue::Int(value) => Ok(value),
+            DataValue::Uint(value) => {
+                i128::try_from(value).map_err(|_| error!(ErrorCode::ValueOutOfRange))
+            }
+        }
+    }
+}
+
+/// A signed DON report for a `Signed` or `Data` feed, as accepted by
+/// `update_data_feed`. `values` replace the benchmark/ask/bid prices of a
+/// price report.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DataReport {
+    pub feed_id: [u8; 32],
+    pub valid_time_stamp: u128,
+    pub observe_time_stamp: u128,
+    pub native_fee: u128,
+    pub apro_token_fee: u128,
+    pub expire_at: u128,
+    pub values: Vec<DataValue>,
+    pub config_digest: [u8; 32],
+    pub epoch_and_round: u128,
+    pub extra_hash: [u8; 32],
+    pub signatures: Vec<[u8; 64]>,
+    pub recovery_ids: Vec<u8>,
+}
+
+impl DataReport {
+    pub fn report_data_hash(&self) -> [u8; 32] {
+        let mut report_data = vec![
+            Token::FixedBytes(self.feed_id.to_vec()),
+            Token::Uint(self.valid_time_stamp.into()),
+            Token::Uint(self.observe_time_stamp.into()),
+            Token::Uint(self.native_fee.into()),
+            Token::Uint(self.apro_token_fee.into()),
+            Token::Uint(self.expire_at.into()),
+        ];
+        report_data.extend(self.values.iter().map(DataValue::token));
+
+        keccak_hash(&encode(&report_data)).to_bytes()
+    }
+
+    pub fn verify(&self, oracle_state: &OracleState, quorum: &Quorum) -> Result<Vec<[u8; 20]>> {
+        let h = signing_hash(
+            &self.report_data_hash(),
+            &self.config_digest,
+            self.epoch_and_round,
+            &self.extra_hash,
+        );
+
+        verify_quorum(
+            oracle_state,
+            quorum,
+            &h,
+            &self.signatures,
+            &self.recovery_ids,
+        )
+    }
+
+    pub fn store(&self, data_feed: &mut DataFeed) -> Result<()> {
+        data_feed.values = self
+            .values
+            .iter()
+            .map(DataValue::to_i128)
+            .collect::<Result<_>>()?;
+        data_feed.feed_id = self.feed_id;
+        data_feed.valid_time_stamp = self.valid_time_stamp;
+        data_feed.observe_time_stamp = self.observe_time_stamp;
+        data_feed.native_fee = self.native_fee;
+        data_feed.apro_token_fee = self.apro_token_fee;
+        data_feed.expire_at = self.expire_at;
+        data_feed.config_digest = self.config_digest;
+        data_feed.epoch_and_round = self.epoch_and_round;
+        data_feed.extra_hash = self.extra_hash;
+        Ok(())
+    }
+}
+
+/// Hash the DON signs: `keccak256(data_hash || abi.encode(report_context))`,
+/// matching the EVM verifier. `data_hash` is either the hash of a single
+/// report or the root of a Merkle tree of report hashes.
+pub fn signing_hash(
+    data_hash: &[u8; 32],
+    config_digest: &[u8; 32],
+    epoch_and_round: u128,
+    extra_hash: &[u8; 32],
+) -> [u8; 32] {
+    let report_context = vec![
+        Token::FixedBytes(config_digest.to_vec()),
+        Token::Uint(epoch_and_round.into()),
+        Token::FixedBytes(extra_hash.to_vec()),
+    ];
+
+    let encoded_report_context = encode(&report_context);
+    let value = [data_hash.as_ref(), &encoded_report_context].concat();
+    msg!("Concatenated Value: 0x{}", hex::encode(&value));
+
+    let h = keccak_hash(&value);
+    msg!("Final Hash (h): 0x{}", hex::encode(h));
+
+    h.to_bytes()
+}
+
+/// Signature requirements of a feed: the oracle state's, unless the feed's
+/// config overrides them. In weight mode a feed's `required_signatures`
+/// override still applies, as a minimum number of distinct signers.
+pub struct Quorum {
+    /// Distinct signers required. In weight mode only a feed's override
+    /// sets it.
+    pub required_signatures: u64,
+    /// `Some` in weight mode: the signers' weights must add up to it.
+    pub weight_threshold: Option<u64>,
+    /// When non-empty, only these oracles count.
+    pub allowed_oracles: Vec<[u8; 20]>,
+}
+
+impl Quorum {
+    pub fn new(oracle_state: &OracleState, feed_config: Option<&FeedConfig>) -> Self {
+        let (required_signatures, weight_threshold, allowed_oracles) = match feed_config {
+            Some(config) => (
+                config.required_signatures,
+                config.weight_threshold,
+                config.allowed_oracles.clone(),
+            ),
+            None => (0, 0, Vec::new()),
+        };
+        let or_default = |value: u64, default: u64| if value > 0 { value } else { default };
+
+        match oracle_state.quorum_mode() {
+            QuorumMode::Count => Quorum {
+                required_signatures: or_default(
+                    required_signatures,
+                    oracle_state.required_signatures,
+                ),
+                weight_threshold: None,
+                allowed_oracles,
+            },
+            QuorumMode::Weight => Quorum {
+                required_signatures,
+                weight_threshold: Some(or_default(weight_threshold, oracle_state.weight_threshold)),
+                allowed_oracles,
+            },
+        }
+    }
+
+    /// Checks `signers`, distinct oracles that count towards the quorum,
+    /// reach it.
+    fn check_met(&self, oracle_state: &OracleState, signers: &[[u8; 20]]) -> Result<()> {
+        require!(
+            signers.len() as u64 >= self.required_signatures,
+            ErrorCode::InsufficientValidSignatures
+        );
+        if let Some(weight_threshold) = self.weight_threshold {
+            require!(
+                oracle_state.weight_of_all(signers) >= weight_threshold,
+                ErrorCode::InsufficientSignatureWeight
+            );
+        }
+        Ok(())
+    }
+
+    pub fn counts(&self, oracle_state: &OracleState, oracle: &[u8; 20]) -> bool {
+        oracle_state.oracles().contains(oracle)
+            && (self.allowed_oracles.is_empty() || self.allowed_oracles.contains(oracle))
+    }
+
+    /// Checks oracles whose signatures were already verified, e.g. those of
+    /// a Merkle root, meet the quorum and returns those that count towards
+    /// it. Oracles removed or rotated out since they signed are left out.
+    pub fn check_signers(
+        &self,
+        oracle_state: &OracleState,
+        signers: &[[u8; 20]],
+    ) -> Result<Vec<[u8; 20]>> {
+        let counted: Vec<[u8; 20]> = signers
+            .iter()
+            .filter(|signer| self.counts(oracle_state, signer))
+            .copied()
+            .collect();
+        self.check_met(oracle_state, &counted)?;
+        Ok(counted)
+    }
+}
+
+/// Checks `signatures` over `hash` carry `quorum` and returns the distinct
+/// oracle addresses that count towards it.
+pub fn verify_quorum(
+    oracle_state: &OracleState,
+    quorum: &Quorum,
+    hash: &[u8; 32],
+    signatures: &[[u8; 64]],
+    recovery_ids: &[u8],
+) -> Result<Vec<[u8; 20]>> {
+    require!(
+        signatures.len() == recovery_ids.len(),
+        ErrorCode::ArrayLengthMismatch
+    );
+
+    require!(
+        signatures.len() as u64 >= quorum.required_signatures,
+        ErrorCode::InsufficientSignatures
+    );
+
+    let verified_oracles = verify_signatures(oracle_state, quorum, hash, signatures, recovery_ids)?;
+    quorum.check_met(oracle_state, &verified_oracles)?;
+
+    Ok(verified_oracles)
+}
+
+/// Recovers the signer of each signature over `hash` and returns the distinct
+/// oracle addresses among them that count towards `quorum`.
+pub fn verify_signatures(
+    oracle_state: &OracleState,
+    quorum: &Quorum,
+    hash: &[u8; 32],
+    signatures: &[[u8; 64]],
+    recovery_ids: &[u8],
+) -> Result<Vec<[u8; 20]>> {
+    let mut verified_oracles: Vec<[u8; 20]> = Vec::new();
+    for (signature, recovery_id) in signatures.iter().zip(recovery_ids) {
+        let recovered_pubkey = secp256k1_recover(hash, *recovery_id, signature)
+            .map_err(|_| ErrorCode::SignatureVerificationFailed)?;
+
+        let recovered_pubkey_bytes = recovered_pubkey.to_bytes();
+
+        let pubkey_hash = keccak_hash(&recovered_pubkey_bytes);
+        let eth_address: [u8; 20] = pubkey_hash.to_bytes()[12..].try_into().unwrap();
+
+        if quorum.counts(oracle_state, &eth_address) && !verified_oracles.contains(&eth_address) {
+            verified_oracles.push(eth_address);
+        }
+
+        msg!("Recovered Ethereum address: 0x{}", hex::encode(eth_address));
+    }
+
+    Ok(verified_oracles)
+}
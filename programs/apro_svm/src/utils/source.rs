@@ -0,0 +1,31 @@
+use crate::states::{BreakerState, FeedConfig, FeedStatus, PriceFeed, PriceSource};
+
+/// A source feed's price as read by aggregate, derived and basket feeds.
+pub struct SourcePrice {
+    pub benchmark_price: u128,
+    pub valid_time_stamp: u128,
+    /// The source's `FeedConfig` decimals, or `None` if it has no config.
+    pub decimals: Option<u8>,
+}
+
+/// The price `price_feed` lends to other feeds, or `None` if it holds an
+/// admin's fallback price, is held by its circuit breaker or is retired at
+/// `now`: such prices must not flow into medians, derived feeds or baskets.
+pub fn source_price(
+    price_feed: &PriceFeed,
+    feed_config: Option<&FeedConfig>,
+    now: i64,
+) -> Option<SourcePrice> {
+    if price_feed.price_source() != PriceSource::Oracle
+        || price_feed.breaker_state() == BreakerState::NeedsConfirmation
+        || feed_config.is_some_and(|config| config.effective_status(now) == FeedStatus::Retired)
+    {
+        return None;
+    }
+
+    Some(SourcePrice {
+        benchmark_price: price_feed.benchmark_price,
+        valid_time_stamp: u128::from(price_feed.valid_time_stamp),
+        decimals: feed_config.map(|config| config.decimals),
+    })
+}
@@ -1,5 +1,7 @@
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
-use oracle_sdk::{load_price_feed_from_account_info, update_price};
+use oracle_sdk::{load_price_feed_from_account_info, update_price, UpdatePriceAccounts};
 
 declare_id!("GzWu85MdbZtVBDcC4Xrmp1dWix7Qo52nUAkD2FjraJ6f");
 
@@ -19,8 +21,8 @@ pub mod oracle_client_example {
         msg!("Ask Price: {}", price_feed.ask_price);
         msg!("Bid Price: {}", price_feed.bid_price);
 
-        let current_timestamp = Clock::get()?.unix_timestamp as u128;
-        let staleness_threshold = 3600u128;
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let staleness_threshold = 3600u64;
 
         //store the price in the price_result account
         let price_result = if current_timestamp - price_feed.valid_time_stamp <= staleness_threshold
@@ -59,13 +61,24 @@ pub mod oracle_client_example {
         recovery_ids: Vec<u8>,
     ) -> Result<()> {
         //update the price feed through CPI
+        let accounts = &ctx.accounts;
         update_price(
-            &ctx.accounts.oracle_state.to_account_info(),
-            &ctx.accounts.price_feed.to_account_info(),
-            &ctx.accounts.payer.to_account_info(),
-            &ctx.accounts.admin.to_account_info(),
-            &ctx.accounts.system_program.to_account_info(),
-            &ctx.accounts.oracle_program.to_account_info(),
+            UpdatePriceAccounts {
+                oracle_state: accounts.oracle_state.to_account_info(),
+                price_feed: accounts.price_feed.to_account_info(),
+                payer: accounts.payer.to_account_info(),
+                admin: accounts.admin.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                oracle_program: accounts.oracle_program.to_account_info(),
+                operator_registry: optional_account_info(&accounts.operator_registry),
+                price_history: optional_account_info(&accounts.price_history),
+                price_accumulator: optional_account_info(&accounts.price_accumulator),
+                feed_registry: optional_account_info(&accounts.feed_registry),
+                feed_registry_page: optional_account_info(&accounts.feed_registry_page),
+                feed_config: optional_account_info(&accounts.feed_config),
+                secondary_oracle_state: optional_account_info(&accounts.secondary_oracle_state),
+                signer_stats: optional_account_info(&accounts.signer_stats),
+            },
             feed_id,
             valid_time_stamp,
             observe_time_stamp,
@@ -85,6 +98,12 @@ pub mod oracle_client_example {
     }
 }
 
+fn optional_account_info<'info>(
+    account: &Option<UncheckedAccount<'info>>,
+) -> Option<AccountInfo<'info>> {
+    account.as_ref().map(|account| account.to_account_info())
+}
+
 #[derive(Accounts)]
 pub struct FetchPrice<'info> {
     #[account(mut)]
@@ -117,6 +136,28 @@ pub struct UpdateOraclePrice<'info> {
     pub system_program: Program<'info, System>,
     /// CHECK: This account is verified in the update_price function
     pub oracle_program: UncheckedAccount<'info>,
+    /// CHECK: This account is verified in the update_price function
+    #[account(mut)]
+    pub operator_registry: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is verified in the update_price function
+    #[account(mut)]
+    pub price_history: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is verified in the update_price function
+    #[account(mut)]
+    pub price_accumulator: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is verified in the update_price function
+    #[account(mut)]
+    pub feed_registry: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is verified in the update_price function
+    #[account(mut)]
+    pub feed_registry_page: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is verified in the update_price function
+    pub feed_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is verified in the update_price function
+    pub secondary_oracle_state: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is verified in the update_price function
+    #[account(mut)]
+    pub signer_stats: Option<UncheckedAccount<'info>>,
 }
 
 #[account]
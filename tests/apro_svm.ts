@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { AproSvm } from "../target/types/apro_svm";
 import { PublicKey, Keypair } from "@solana/web3.js";
+import { ethers } from "ethers";
 import { expect } from "chai";
 
 describe("apro_svm", () => {
@@ -49,6 +50,13 @@ describe("apro_svm", () => {
     hexToUint8Array("0060c22f693a7023f4ab0f484b234a55b1fd0feb"),
   ];
 
+  function feedConfigAddress(oracleState: PublicKey): PublicKey {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("feed_config"), oracleState.toBuffer(), feedId],
+      program.programId,
+    )[0];
+  }
+
   async function setupOracleState(
     oracleStateId: anchor.BN,
     requiredSignatures: anchor.BN,
@@ -85,6 +93,96 @@ describe("apro_svm", () => {
     return { oracleStatePda };
   }
 
+  // Sets up an oracle state whose only oracle is a local `signer`, so tests
+  // can sign their own reports.
+  async function setupSignerOracleState(
+    oracleStateId: anchor.BN,
+    signer: ethers.HDNodeWallet,
+    admin: Keypair,
+  ): Promise<{ oracleStatePda: PublicKey }> {
+    const [oracleStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_state"), oracleStateId.toBuffer("le", 8)],
+      program.programId,
+    );
+
+    await program.methods
+      .initializeOracle(oracleStateId, new anchor.BN(1), new anchor.BN(3600))
+      .accounts({
+        oracleState: oracleStatePda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .addOracle(Array.from(hexToUint8Array(signer.address)))
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { oracleStatePda };
+  }
+
+  // Signs a price report with a local key, as a single DON oracle would.
+  // `reportTime` is the report's `validTimeStamp`.
+  function signPriceReport(
+    signer: ethers.HDNodeWallet,
+    prices: { benchmark: anchor.BN; ask: anchor.BN; bid: anchor.BN },
+    reportTime: anchor.BN = validTimeStamp,
+  ): { signatures: number[][]; recoveryIds: Buffer } {
+    const coder = ethers.AbiCoder.defaultAbiCoder();
+    // The valid timestamp is encoded as a uint256, which in range is the
+    // same 32 bytes as a uint32, so tests can sign timestamps out of range.
+    const reportDataHash = ethers.keccak256(
+      coder.encode(
+        [
+          "bytes32",
+          "uint256",
+          "uint32",
+          "uint192",
+          "uint192",
+          "uint32",
+          "int192",
+          "int192",
+          "int192",
+        ],
+        [
+          feedId,
+          reportTime.toString(),
+          observeTimeStamp.toString(),
+          nativeFee.toString(),
+          aproTokenFee.toString(),
+          expireAt.toString(),
+          prices.benchmark.toString(),
+          prices.ask.toString(),
+          prices.bid.toString(),
+        ],
+      ),
+    );
+    const signature = signer.signingKey.sign(
+      ethers.keccak256(
+        ethers.concat([
+          reportDataHash,
+          coder.encode(
+            ["bytes32", "uint256", "bytes32"],
+            [configDigest, epochAndRound.toString(), extraHash],
+          ),
+        ]),
+      ),
+    );
+
+    return {
+      signatures: [
+        Array.from(ethers.getBytes(ethers.concat([signature.r, signature.s]))),
+      ],
+      recoveryIds: Buffer.from([signature.yParity]),
+    };
+  }
+
   it("Initializes the oracle state successfully", async () => {
     const admin = Keypair.generate();
     const oracleStateId = new anchor.BN(1);
@@ -104,8 +202,12 @@ describe("apro_svm", () => {
     expect(oracleState.expirationPeriod.toNumber()).to.equal(7200);
     expect(oracleState.totalOracles.toNumber()).to.equal(2);
 
-    expect(oracleState.oracles.length).to.equal(expectedEthAddresses.length);
-    oracleState.oracles.forEach((oracle, index) => {
+    const oracles = oracleState.oracles.slice(
+      0,
+      oracleState.totalOracles.toNumber(),
+    );
+    expect(oracles.length).to.equal(expectedEthAddresses.length);
+    oracles.forEach((oracle, index) => {
       expect(Buffer.from(oracle).toString("hex")).to.equal(
         Buffer.from(expectedEthAddresses[index]).toString("hex"),
       );
@@ -220,6 +322,15 @@ describe("apro_svm", () => {
         payer: provider.wallet.publicKey,
         admin: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: null,
+        priceHistory: null,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        // Clients written before feed configs leave it out.
+        feedConfig: null,
+        secondaryOracleState: null,
+        signerStats: null,
       })
       .rpc();
 
@@ -240,11 +351,24 @@ describe("apro_svm", () => {
     );
     expect(priceFeed.askPrice.toString()).to.equal(askPrice.toString());
     expect(priceFeed.bidPrice.toString()).to.equal(bidPrice.toString());
+    expect(priceFeed.confidence.toString()).to.equal(
+      askPrice.sub(bidPrice).divn(2).toString(),
+    );
+    expect(priceFeed.spreadBps.toString()).to.equal(
+      askPrice.sub(bidPrice).muln(10000).div(benchmarkPrice).toString(),
+    );
     expect(Uint8Array.from(priceFeed.configDigest)).to.deep.equal(configDigest);
     expect(priceFeed.epochAndRound.toString()).to.equal(
       epochAndRound.toString(),
     );
     expect(Uint8Array.from(priceFeed.extraHash)).to.deep.equal(extraHash);
+    expect(priceFeed.signerBitmap).to.equal(0b11);
+    expect(priceFeed.updateCount.toNumber()).to.equal(1);
+    expect(priceFeed.updater.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58(),
+    );
+    expect(priceFeed.lastUpdateSlot.toNumber()).to.be.greaterThan(0);
+    expect(priceFeed.lastUpdateUnixTs.toNumber()).to.be.greaterThan(0);
   });
 
   it("Fails to update price with mismatched signature and recovery_id lengths", async () => {
@@ -291,6 +415,14 @@ describe("apro_svm", () => {
           payer: provider.wallet.publicKey,
           admin: admin.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
         })
         .rpc();
       expect.fail("Expected an error but none was thrown");
@@ -341,6 +473,14 @@ describe("apro_svm", () => {
           payer: provider.wallet.publicKey,
           admin: admin.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
         })
         .rpc();
       expect.fail("Expected an error but none was thrown");
@@ -400,6 +540,14 @@ describe("apro_svm", () => {
           payer: provider.wallet.publicKey,
           admin: admin.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
         })
         .rpc();
       expect.fail("Expected an error but none was thrown");
@@ -461,6 +609,14 @@ describe("apro_svm", () => {
           payer: provider.wallet.publicKey,
           admin: admin.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
         })
         .rpc();
       expect.fail("Expected an error but none was thrown");
@@ -471,6 +627,3502 @@ describe("apro_svm", () => {
     }
   });
 
+  it("Credits operator rewards to verified signers and lets them claim", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(30);
+    const requiredSignatures = new anchor.BN(2);
+    const expirationPeriod = new anchor.BN(3600);
+    const payouts = [Keypair.generate(), Keypair.generate()];
+    const operatorFeeBps = new anchor.BN(5000);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    // Fee recipients must stay rent exempt after receiving their share.
+    for (const account of [admin, ...payouts]) {
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            toPubkey: account.publicKey,
+            lamports: anchor.web3.LAMPORTS_PER_SOL,
+          }),
+        ),
+      );
+    }
+
+    const [operatorRegistryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("operator_registry"), oracleStatePda.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .initializeOperatorRegistry(operatorFeeBps)
+      .accounts({
+        oracleState: oracleStatePda,
+        operatorRegistry: operatorRegistryPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    for (const [index, ethAddress] of expectedEthAddresses.entries()) {
+      await program.methods
+        .setOperator(Array.from(ethAddress), payouts[index].publicKey)
+        .accounts({
+          oracleState: oracleStatePda,
+          operatorRegistry: operatorRegistryPda,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    }
+
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .updatePrice(
+        feedId,
+        validTimeStamp,
+        observeTimeStamp,
+        nativeFee,
+        aproTokenFee,
+        expireAt,
+        benchmarkPrice,
+        askPrice,
+        bidPrice,
+        configDigest,
+        epochAndRound,
+        extraHash,
+        signatures,
+        recoveryIds,
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: operatorRegistryPda,
+        priceHistory: null,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        secondaryOracleState: null,
+        signerStats: null,
+      })
+      .rpc();
+
+    // Half of the 1_000_000 lamport fee is split between the two signers.
+    const operatorRegistry = await program.account.operatorRegistry.fetch(
+      operatorRegistryPda,
+    );
+    operatorRegistry.operators.forEach((operator) => {
+      expect(operator.accruedRewards.toNumber()).to.equal(250000);
+    });
+
+    const balanceBefore = await provider.connection.getBalance(
+      payouts[0].publicKey,
+    );
+    await program.methods
+      .claimRewards(Array.from(expectedEthAddresses[0]))
+      .accounts({
+        oracleState: oracleStatePda,
+        operatorRegistry: operatorRegistryPda,
+        payout: payouts[0].publicKey,
+      })
+      .signers([payouts[0]])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(
+      payouts[0].publicKey,
+    );
+    expect(balanceAfter - balanceBefore).to.equal(250000);
+
+    try {
+      await program.methods
+        .claimRewards(Array.from(expectedEthAddresses[1]))
+        .accounts({
+          oracleState: oracleStatePda,
+          operatorRegistry: operatorRegistryPda,
+          payout: payouts[0].publicKey,
+        })
+        .signers([payouts[0]])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnauthorizedOperator");
+    }
+  });
+
+  it("Appends updates to the price history", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(31);
+    const requiredSignatures = new anchor.BN(2);
+    const expirationPeriod = new anchor.BN(3600);
+    const capacity = new anchor.BN(4);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+    const [priceHistoryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_history"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .initializePriceHistory(Array.from(feedId), capacity)
+      .accounts({
+        oracleState: oracleStatePda,
+        priceHistory: priceHistoryPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const updatePrice = () =>
+      program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: priceHistoryPda,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+
+    await updatePrice();
+
+    const priceHistory = await program.account.priceHistory.fetch(
+      priceHistoryPda,
+    );
+    expect(priceHistory.capacity.toNumber()).to.equal(4);
+    expect(priceHistory.len.toNumber()).to.equal(1);
+    expect(priceHistory.head.toNumber()).to.equal(1);
+
+    // Entries follow the 8 byte discriminator and the 88 byte header.
+    const info = await provider.connection.getAccountInfo(priceHistoryPda);
+    const entry = info.data.subarray(96, 96 + 80);
+    expect(
+      new anchor.BN(entry.subarray(0, 16), "le").toString(),
+    ).to.equal(validTimeStamp.toString());
+    expect(
+      new anchor.BN(entry.subarray(16, 32), "le").toString(),
+    ).to.equal(benchmarkPrice.toString());
+
+    // Replaying the report must not add an out-of-order entry.
+    try {
+      await updatePrice();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("StaleReport");
+    }
+  });
+
+  it("Seeds the TWAP and EMA accumulators on the first update", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(32);
+    const requiredSignatures = new anchor.BN(2);
+    const expirationPeriod = new anchor.BN(3600);
+    const emaPeriod = new anchor.BN(600);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+    const [priceAccumulatorPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_accumulator"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .initializePriceAccumulator(Array.from(feedId), emaPeriod)
+      .accounts({
+        oracleState: oracleStatePda,
+        priceAccumulator: priceAccumulatorPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .updatePrice(
+        feedId,
+        validTimeStamp,
+        observeTimeStamp,
+        nativeFee,
+        aproTokenFee,
+        expireAt,
+        benchmarkPrice,
+        askPrice,
+        bidPrice,
+        configDigest,
+        epochAndRound,
+        extraHash,
+        signatures,
+        recoveryIds,
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: null,
+        priceHistory: null,
+        priceAccumulator: priceAccumulatorPda,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        secondaryOracleState: null,
+        signerStats: null,
+      })
+      .rpc();
+
+    const priceAccumulator = await program.account.priceAccumulator.fetch(
+      priceAccumulatorPda,
+    );
+    expect(priceAccumulator.observationLen).to.equal(1);
+    expect(priceAccumulator.lastTimestamp.toString()).to.equal(
+      validTimeStamp.toString(),
+    );
+    expect(priceAccumulator.lastPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+    expect(priceAccumulator.emaPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+    expect(priceAccumulator.cumulativePrice.toString()).to.equal("0");
+  });
+
+  it("Sets feed metadata and status", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(33);
+    const requiredSignatures = new anchor.BN(2);
+    const expirationPeriod = new anchor.BN(3600);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    const [feedConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("feed_config"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "ETH", "USD", "Ether / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .setFeedStatus(Array.from(feedId), { deprecated: {} })
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        feedRegistryPage: null,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const feedConfig = await program.account.feedConfig.fetch(feedConfigPda);
+    expect(feedConfig.decimals).to.equal(18);
+    expect(feedConfig.baseSymbol).to.equal("ETH");
+    expect(feedConfig.quoteSymbol).to.equal("USD");
+    expect(feedConfig.description).to.equal("Ether / US Dollar");
+    expect(feedConfig.status).to.deep.equal({ deprecated: {} });
+  });
+
+  it("Updates existing feeds in a batch", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(34),
+      signer,
+      admin,
+    );
+    const prices = { benchmark: benchmarkPrice, ask: askPrice, bid: bidPrice };
+    const first = signPriceReport(signer, prices);
+
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+    const [priceHistoryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_history"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+    const [priceAccumulatorPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_accumulator"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .initializePriceHistory(Array.from(feedId), new anchor.BN(4))
+      .accounts({
+        oracleState: oracleStatePda,
+        priceHistory: priceHistoryPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .updatePrice(
+        feedId,
+        validTimeStamp,
+        observeTimeStamp,
+        nativeFee,
+        aproTokenFee,
+        expireAt,
+        benchmarkPrice,
+        askPrice,
+        bidPrice,
+        configDigest,
+        epochAndRound,
+        extraHash,
+        first.signatures,
+        first.recoveryIds,
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: null,
+        priceHistory: priceHistoryPda,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        secondaryOracleState: null,
+        signerStats: null,
+      })
+      .rpc();
+
+    // The batch carries a newer report for the feed.
+    const batchTime = validTimeStamp.addn(1);
+    const batched = signPriceReport(signer, prices, batchTime);
+    const report = {
+      feedId: Array.from(feedId),
+      validTimeStamp: batchTime,
+      observeTimeStamp,
+      nativeFee,
+      aproTokenFee,
+      expireAt,
+      benchmarkPrice,
+      askPrice,
+      bidPrice,
+      configDigest: Array.from(configDigest),
+      epochAndRound,
+      extraHash: Array.from(extraHash),
+      signatures: batched.signatures,
+      recoveryIds: batched.recoveryIds,
+    };
+
+    const adminBalanceBefore = await provider.connection.getBalance(
+      admin.publicKey,
+    );
+    await program.methods
+      .updatePrices([report])
+      .accounts({
+        oracleState: oracleStatePda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: null,
+        signerStats: null,
+      })
+      .remainingAccounts([
+        { pubkey: priceFeedPda, isWritable: true, isSigner: false },
+        {
+          pubkey: feedConfigAddress(oracleStatePda),
+          isWritable: false,
+          isSigner: false,
+        },
+        // The feed has a history but no accumulator.
+        { pubkey: priceHistoryPda, isWritable: true, isSigner: false },
+        { pubkey: priceAccumulatorPda, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+    const adminBalanceAfter = await provider.connection.getBalance(
+      admin.publicKey,
+    );
+    expect(adminBalanceAfter - adminBalanceBefore).to.equal(1000000);
+
+    // The batched report is appended after the first one.
+    const priceHistory = await program.account.priceHistory.fetch(
+      priceHistoryPda,
+    );
+    expect(priceHistory.len.toNumber()).to.equal(2);
+    const info = await provider.connection.getAccountInfo(priceHistoryPda);
+    const entry = info.data.subarray(96 + 80, 96 + 2 * 80);
+    expect(
+      new anchor.BN(entry.subarray(0, 16), "le").toString(),
+    ).to.equal(batchTime.toString());
+
+    try {
+      await program.methods
+        .updatePrices([report])
+        .accounts({
+          oracleState: oracleStatePda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          signerStats: null,
+        })
+        .remainingAccounts([])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("PriceFeedAccountMismatch");
+    }
+  });
+
+  it("Applies a report against a submitted Merkle root", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(35);
+    const requiredSignatures = new anchor.BN(2);
+    const expirationPeriod = new anchor.BN(3600);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    // A single-leaf tree: the root is the report data hash, which the DON
+    // signatures above already cover.
+    const root = hexToUint8Array(
+      ethers.keccak256(
+        ethers.AbiCoder.defaultAbiCoder().encode(
+          [
+            "bytes32",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+          ],
+          [
+            feedId,
+            validTimeStamp.toString(),
+            observeTimeStamp.toString(),
+            nativeFee.toString(),
+            aproTokenFee.toString(),
+            expireAt.toString(),
+            benchmarkPrice.toString(),
+            askPrice.toString(),
+            bidPrice.toString(),
+          ],
+        ),
+      ),
+    );
+
+    const [merkleRootPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("merkle_root"), oracleStatePda.toBuffer(), root],
+      program.programId,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .submitMerkleRoot(
+        Array.from(root),
+        Array.from(configDigest),
+        epochAndRound,
+        Array.from(extraHash),
+        signatures.map((sig) => Array.from(sig)),
+        recoveryIds,
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        merkleRoot: merkleRootPda,
+        payer: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const merkleRoot = await program.account.merkleRoot.fetch(merkleRootPda);
+    expect(merkleRoot.signers.length).to.equal(2);
+
+    const updateWithProof = (price: anchor.BN) =>
+      program.methods
+        .updatePriceWithProof(
+          Array.from(feedId),
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          price,
+          askPrice,
+          bidPrice,
+          [],
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          merkleRoot: merkleRootPda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          signerStats: null,
+        })
+        .rpc();
+
+    await updateWithProof(benchmarkPrice);
+
+    const priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+    expect(priceFeed.epochAndRound.toString()).to.equal(
+      epochAndRound.toString(),
+    );
+
+    try {
+      await updateWithProof(benchmarkPrice.addn(1));
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidMerkleProof");
+    }
+
+    try {
+      await program.methods
+        .closeMerkleRoot()
+        .accounts({
+          merkleRoot: merkleRootPda,
+          payer: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("MerkleRootNotExpired");
+    }
+  });
+
+  it("Only rewards oracles still registered for a Merkle root's signers", async () => {
+    const admin = Keypair.generate();
+    const { oracleStatePda } = await setupOracleState(
+      new anchor.BN(62),
+      new anchor.BN(1),
+      new anchor.BN(3600),
+      admin,
+    );
+
+    // The admin must stay rent exempt after receiving its share of the fee.
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: admin.publicKey,
+          lamports: anchor.web3.LAMPORTS_PER_SOL,
+        }),
+      ),
+    );
+
+    const [operatorRegistryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("operator_registry"), oracleStatePda.toBuffer()],
+      program.programId,
+    );
+    await program.methods
+      .initializeOperatorRegistry(new anchor.BN(5000))
+      .accounts({
+        oracleState: oracleStatePda,
+        operatorRegistry: operatorRegistryPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    for (const ethAddress of expectedEthAddresses) {
+      await program.methods
+        .setOperator(Array.from(ethAddress), Keypair.generate().publicKey)
+        .accounts({
+          oracleState: oracleStatePda,
+          operatorRegistry: operatorRegistryPda,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    }
+
+    // A single-leaf tree: the root is the report data hash.
+    const root = hexToUint8Array(
+      ethers.keccak256(
+        ethers.AbiCoder.defaultAbiCoder().encode(
+          [
+            "bytes32",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+            "uint256",
+          ],
+          [
+            feedId,
+            validTimeStamp.toString(),
+            observeTimeStamp.toString(),
+            nativeFee.toString(),
+            aproTokenFee.toString(),
+            expireAt.toString(),
+            benchmarkPrice.toString(),
+            askPrice.toString(),
+            bidPrice.toString(),
+          ],
+        ),
+      ),
+    );
+    const [merkleRootPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("merkle_root"), oracleStatePda.toBuffer(), root],
+      program.programId,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .submitMerkleRoot(
+        Array.from(root),
+        Array.from(configDigest),
+        epochAndRound,
+        Array.from(extraHash),
+        signatures.map((sig) => Array.from(sig)),
+        recoveryIds,
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        merkleRoot: merkleRootPda,
+        payer: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // The second signer is rotated out after signing the root.
+    await program.methods
+      .rotateOracle(
+        Array.from(expectedEthAddresses[1]),
+        Array.from(hexToUint8Array(ethers.Wallet.createRandom().address)),
+        new anchor.BN(1),
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .updatePriceWithProof(
+        Array.from(feedId),
+        validTimeStamp,
+        observeTimeStamp,
+        nativeFee,
+        aproTokenFee,
+        expireAt,
+        benchmarkPrice,
+        askPrice,
+        bidPrice,
+        [],
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        merkleRoot: merkleRootPda,
+        priceFeed: priceFeedPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: operatorRegistryPda,
+        priceHistory: null,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        signerStats: null,
+      })
+      .rpc();
+
+    // Half of the 1_000_000 lamport fee goes to the one remaining signer.
+    const operatorRegistry = await program.account.operatorRegistry.fetch(
+      operatorRegistryPda,
+    );
+    expect(
+      operatorRegistry.operators.map((operator) =>
+        operator.accruedRewards.toNumber(),
+      ),
+    ).to.deep.equal([500000, 0]);
+    const priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.signerBitmap).to.equal(0b1);
+  });
+
+  it("Records created feeds in the feed registry", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(36);
+    const requiredSignatures = new anchor.BN(2);
+    const expirationPeriod = new anchor.BN(3600);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    const [feedRegistryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("feed_registry"), oracleStatePda.toBuffer()],
+      program.programId,
+    );
+    const [feedRegistryPagePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("feed_registry_page"),
+        oracleStatePda.toBuffer(),
+        new anchor.BN(0).toBuffer("le", 4),
+      ],
+      program.programId,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .initializeFeedRegistry()
+      .accounts({
+        oracleState: oracleStatePda,
+        feedRegistry: feedRegistryPda,
+        feedRegistryPage: feedRegistryPagePda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const updatePrice = (feedRegistry, feedRegistryPage) =>
+      program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry,
+          feedRegistryPage,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+
+    try {
+      await updatePrice(null, null);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("FeedRegistryRequired");
+    }
+
+    await updatePrice(feedRegistryPda, feedRegistryPagePda);
+    // An existing feed needs no registry accounts; the replayed report gets
+    // past the registry check and is only refused as stale.
+    try {
+      await updatePrice(null, null);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("StaleReport");
+    }
+
+    const feedRegistry = await program.account.feedRegistry.fetch(
+      feedRegistryPda,
+    );
+    expect(feedRegistry.pageCount).to.equal(1);
+    expect(feedRegistry.feedCount.toNumber()).to.equal(1);
+
+    let page = await program.account.feedRegistryPage.fetch(
+      feedRegistryPagePda,
+    );
+    expect(page.feeds.length).to.equal(1);
+    expect(Buffer.from(page.feeds[0].feedId)).to.deep.equal(
+      Buffer.from(feedId),
+    );
+    expect(page.feeds[0].createdAt.toNumber()).to.be.greaterThan(0);
+    expect(page.feeds[0].status).to.deep.equal({ active: {} });
+
+    // Status changes reach the registry entry through its page.
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "ETH", "USD", "Ether / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .setFeedStatus(Array.from(feedId), { deprecated: {} })
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        feedRegistryPage: feedRegistryPagePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+    page = await program.account.feedRegistryPage.fetch(feedRegistryPagePda);
+    expect(page.feeds[0].status).to.deep.equal({ deprecated: {} });
+  });
+
+  it("Only creates registered feeds when required", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(37);
+    const requiredSignatures = new anchor.BN(2);
+    const expirationPeriod = new anchor.BN(3600);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    const [feedConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("feed_config"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .setRegisteredFeedsOnly(true)
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const updatePrice = () =>
+      program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigPda,
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+
+    try {
+      await updatePrice();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnregisteredFeed");
+    }
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "ETH", "USD", "Ether / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await updatePrice();
+
+    const priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+  });
+
+  it("Updates a data feed with signed values", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(38);
+    const signer = ethers.Wallet.createRandom();
+
+    const [oracleStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_state"), oracleStateId.toBuffer("le", 8)],
+      program.programId,
+    );
+    await program.methods
+      .initializeOracle(oracleStateId, new anchor.BN(1), new anchor.BN(3600))
+      .accounts({
+        oracleState: oracleStatePda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .addOracle(Array.from(hexToUint8Array(signer.address)))
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const [feedConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("feed_config"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+    const [dataFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("data_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 8, "BTC", "USD", "BTC perp funding")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .setFeedKind(Array.from(feedId), { data: {} }, [
+        "funding_rate",
+        "open_interest",
+      ])
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    // Sign the report exactly as the EVM verifier would hash it.
+    const fundingRate = BigInt(-1234);
+    const openInterest = BigInt(5678);
+    const coder = ethers.AbiCoder.defaultAbiCoder();
+    const reportDataHash = ethers.keccak256(
+      coder.encode(
+        [
+          "bytes32",
+          "uint32",
+          "uint32",
+          "uint192",
+          "uint192",
+          "uint32",
+          "int192",
+          "uint192",
+        ],
+        [
+          feedId,
+          validTimeStamp.toString(),
+          observeTimeStamp.toString(),
+          nativeFee.toString(),
+          aproTokenFee.toString(),
+          expireAt.toString(),
+          fundingRate,
+          openInterest,
+        ],
+      ),
+    );
+    const hash = ethers.keccak256(
+      ethers.concat([
+        reportDataHash,
+        coder.encode(
+          ["bytes32", "uint256", "bytes32"],
+          [configDigest, epochAndRound.toString(), extraHash],
+        ),
+      ]),
+    );
+    const signature = signer.signingKey.sign(hash);
+
+    const report = {
+      feedId: Array.from(feedId),
+      validTimeStamp,
+      observeTimeStamp,
+      nativeFee,
+      aproTokenFee,
+      expireAt,
+      values: [
+        { int: { "0": new anchor.BN(fundingRate.toString()) } },
+        { uint: { "0": new anchor.BN(openInterest.toString()) } },
+      ],
+      configDigest: Array.from(configDigest),
+      epochAndRound,
+      extraHash: Array.from(extraHash),
+      signatures: [
+        Array.from(ethers.getBytes(ethers.concat([signature.r, signature.s]))),
+      ],
+      recoveryIds: Buffer.from([signature.yParity]),
+    };
+
+    const [signerStatsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("signer_stats"), oracleStatePda.toBuffer()],
+      program.programId,
+    );
+    await program.methods
+      .initializeSignerStats()
+      .accounts({
+        oracleState: oracleStatePda,
+        signerStats: signerStatsPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const updateDataFeed = (signerStats: anchor.web3.PublicKey | null) =>
+      program.methods
+        .updateDataFeed(report)
+        .accounts({
+          oracleState: oracleStatePda,
+          feedConfig: feedConfigPda,
+          dataFeed: dataFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          signerStats,
+        })
+        .rpc();
+
+    // Once initialized, the signer stats must record data reports too.
+    try {
+      await updateDataFeed(null);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("SignerStatsRequired");
+    }
+
+    await updateDataFeed(signerStatsPda);
+    const signerStats = await program.account.signerStats.fetch(
+      signerStatsPda,
+    );
+    expect(signerStats.reportCount.toNumber()).to.equal(1);
+
+    const dataFeed = await program.account.dataFeed.fetch(dataFeedPda);
+    expect(dataFeed.values.map((value) => value.toString())).to.deep.equal([
+      "-1234",
+      "5678",
+    ]);
+
+    try {
+      await program.methods
+        .updateDataFeed({ ...report, values: report.values.slice(0, 1) })
+        .accounts({
+          oracleState: oracleStatePda,
+          feedConfig: feedConfigPda,
+          dataFeed: dataFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          signerStats: signerStatsPda,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("FieldCountMismatch");
+    }
+
+    // Replaying the stored report cannot roll the feed back.
+    try {
+      await updateDataFeed(signerStatsPda);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("StaleReport");
+    }
+  });
+
+  it("Validates price ordering against the configured tolerance", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(39);
+    const signer = ethers.Wallet.createRandom();
+
+    const [oracleStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_state"), oracleStateId.toBuffer("le", 8)],
+      program.programId,
+    );
+    await program.methods
+      .initializeOracle(oracleStateId, new anchor.BN(1), new anchor.BN(3600))
+      .accounts({
+        oracleState: oracleStatePda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .addOracle(Array.from(hexToUint8Array(signer.address)))
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    // The bid crosses the benchmark by 1%.
+    const prices = {
+      benchmark: new anchor.BN(100000),
+      ask: new anchor.BN(102000),
+      bid: new anchor.BN(101000),
+    };
+    const { signatures: crossedSignatures, recoveryIds: crossedRecoveryIds } =
+      signPriceReport(signer, prices);
+
+    const updatePrice = () =>
+      program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          prices.benchmark,
+          prices.ask,
+          prices.bid,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          crossedSignatures,
+          crossedRecoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+
+    try {
+      await updatePrice();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidPriceOrdering");
+    }
+
+    await program.methods
+      .setPriceOrderingTolerance(new anchor.BN(200))
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    await updatePrice();
+
+    const priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.confidence.toString()).to.equal("500");
+    expect(priceFeed.spreadBps.toString()).to.equal("100");
+  });
+
+  it("Holds large moves until the circuit breaker is acknowledged", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(40),
+      signer,
+      admin,
+    );
+
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    // Each report is a second newer than the previous one.
+    let reportTime = validTimeStamp;
+    const updatePrice = (benchmark: number) => {
+      const prices = {
+        benchmark: new anchor.BN(benchmark),
+        ask: new anchor.BN(benchmark + 1000),
+        bid: new anchor.BN(benchmark - 1000),
+      };
+      reportTime = reportTime.addn(1);
+      const { signatures, recoveryIds } = signPriceReport(
+        signer,
+        prices,
+        reportTime,
+      );
+      return program.methods
+        .updatePrice(
+          feedId,
+          reportTime,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          prices.benchmark,
+          prices.ask,
+          prices.bid,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    };
+
+    await updatePrice(100000);
+
+    await program.methods
+      .setCircuitBreaker(Array.from(feedId), {
+        maxDeviationBps: new anchor.BN(1000),
+        maxRateBpsPerSec: new anchor.BN(0),
+        action: { requireConfirmation: {} },
+        confirmationSignatures: new anchor.BN(2),
+      })
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    // A 50% move is held back, without charging the update fee.
+    const adminBalanceBefore = await provider.connection.getBalance(
+      admin.publicKey,
+    );
+    await updatePrice(150000);
+    let priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal("100000");
+    expect(priceFeed.breakerState).to.equal(1);
+    expect(await provider.connection.getBalance(admin.publicKey)).to.equal(
+      adminBalanceBefore,
+    );
+
+    // A report within the limits releases the feed.
+    await updatePrice(105000);
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal("105000");
+    expect(priceFeed.breakerState).to.equal(0);
+
+    await updatePrice(150000);
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.breakerState).to.equal(1);
+
+    await program.methods
+      .acknowledgeCircuitBreaker(Array.from(feedId))
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    await updatePrice(150000);
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal("150000");
+    expect(priceFeed.breakerState).to.equal(0);
+  });
+
+  it("Lets the admin release a circuit breaker in reject mode", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(56),
+      signer,
+      admin,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    let reportTime = validTimeStamp;
+    const updatePrice = (benchmark: number) => {
+      const prices = {
+        benchmark: new anchor.BN(benchmark),
+        ask: new anchor.BN(benchmark + 1000),
+        bid: new anchor.BN(benchmark - 1000),
+      };
+      reportTime = reportTime.addn(1);
+      const { signatures, recoveryIds } = signPriceReport(
+        signer,
+        prices,
+        reportTime,
+      );
+      return program.methods
+        .updatePrice(
+          feedId,
+          reportTime,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          prices.benchmark,
+          prices.ask,
+          prices.bid,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    };
+
+    await updatePrice(100000);
+    await program.methods
+      .setCircuitBreaker(Array.from(feedId), {
+        maxDeviationBps: new anchor.BN(1000),
+        maxRateBpsPerSec: new anchor.BN(0),
+        action: { reject: {} },
+        confirmationSignatures: new anchor.BN(0),
+      })
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    // The market moved: every report deviates from the stored price.
+    await updatePrice(150000);
+    await updatePrice(151000);
+    let priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal("100000");
+    expect(priceFeed.breakerState).to.equal(1);
+
+    await program.methods
+      .acknowledgeCircuitBreaker(Array.from(feedId))
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    await updatePrice(152000);
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal("152000");
+    expect(priceFeed.breakerState).to.equal(0);
+  });
+
+  it("Confirms circuit breaker moves by signer weight", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const heavySigner = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(57),
+      signer,
+      admin,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .addWeightedOracle(
+        Array.from(hexToUint8Array(heavySigner.address)),
+        new anchor.BN(3),
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .setQuorumMode({ weight: {} }, new anchor.BN(1))
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    let reportTime = validTimeStamp;
+    const updatePrice = (
+      reportSigner: ethers.HDNodeWallet,
+      benchmark: number,
+    ) => {
+      const prices = {
+        benchmark: new anchor.BN(benchmark),
+        ask: new anchor.BN(benchmark + 1000),
+        bid: new anchor.BN(benchmark - 1000),
+      };
+      reportTime = reportTime.addn(1);
+      const { signatures, recoveryIds } = signPriceReport(
+        reportSigner,
+        prices,
+        reportTime,
+      );
+      return program.methods
+        .updatePrice(
+          feedId,
+          reportTime,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          prices.benchmark,
+          prices.ask,
+          prices.bid,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    };
+
+    await updatePrice(signer, 100000);
+    await program.methods
+      .setCircuitBreaker(Array.from(feedId), {
+        maxDeviationBps: new anchor.BN(1000),
+        maxRateBpsPerSec: new anchor.BN(0),
+        action: { requireConfirmation: {} },
+        confirmationSignatures: new anchor.BN(3),
+      })
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    // One signature weighing 1 does not confirm the move, one weighing 3
+    // does.
+    await updatePrice(signer, 150000);
+    let priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.breakerState).to.equal(1);
+
+    await updatePrice(heavySigner, 150000);
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal("150000");
+    expect(priceFeed.breakerState).to.equal(0);
+  });
+
+  it("Enforces a per-feed quorum override", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(41);
+    const requiredSignatures = new anchor.BN(1);
+    const expirationPeriod = new anchor.BN(3600);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    const feedConfigPda = feedConfigAddress(oracleStatePda);
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "BTC", "USD", "Bitcoin / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const setFeedQuorum = (
+      required: number,
+      allowed: Uint8Array[],
+      weightThreshold = 0,
+    ) =>
+      program.methods
+        .setFeedQuorum(
+          Array.from(feedId),
+          new anchor.BN(required),
+          new anchor.BN(weightThreshold),
+          allowed.map((oracle) => Array.from(oracle)),
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          feedConfig: feedConfigPda,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+    try {
+      await setFeedQuorum(2, [expectedEthAddresses[0]]);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidFeedQuorum");
+    }
+
+    // Both oracles together only weigh 2.
+    try {
+      await setFeedQuorum(2, [], 3);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidFeedQuorum");
+    }
+
+    await setFeedQuorum(2, []);
+
+    const updatePrice = (signatureCount: number, feedConfig = feedConfigPda) =>
+      program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures.slice(0, signatureCount),
+          recoveryIds.subarray(0, signatureCount),
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig,
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+
+    // A feed config passed in must be the feed's own.
+    try {
+      await updatePrice(2, Keypair.generate().publicKey);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+
+    try {
+      await updatePrice(1);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InsufficientSignatures");
+    }
+
+    await updatePrice(2);
+  });
+
+  it("Enforces a weighted signer quorum", async () => {
+    const admin = Keypair.generate();
+    const oracleStateId = new anchor.BN(42);
+    const requiredSignatures = new anchor.BN(2);
+    const expirationPeriod = new anchor.BN(3600);
+
+    const { oracleStatePda } = await setupOracleState(
+      oracleStateId,
+      requiredSignatures,
+      expirationPeriod,
+      admin,
+    );
+
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    const setQuorumMode = (mode: object, weightThreshold: number) =>
+      program.methods
+        .setQuorumMode(mode as any, new anchor.BN(weightThreshold))
+        .accounts({
+          oracleState: oracleStatePda,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+    try {
+      await setQuorumMode({ weight: {} }, 0);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidWeightThreshold");
+    }
+
+    const setOracleWeight = (oracle: Uint8Array, weight: number) =>
+      program.methods
+        .setOracleWeight(Array.from(oracle), new anchor.BN(weight))
+        .accounts({
+          oracleState: oracleStatePda,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+    await setOracleWeight(expectedEthAddresses[0], 3);
+
+    // The oracles weigh 4 in total.
+    try {
+      await setQuorumMode({ weight: {} }, 5);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal(
+        "UnreachableWeightThreshold",
+      );
+    }
+
+    await setQuorumMode({ weight: {} }, 4);
+
+    try {
+      await setOracleWeight(expectedEthAddresses[0], 2);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal(
+        "UnreachableWeightThreshold",
+      );
+    }
+
+    const updatePrice = (signatureCount: number) =>
+      program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures.slice(0, signatureCount),
+          recoveryIds.subarray(0, signatureCount),
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+
+    // The first oracle alone weighs 3 of the 4 required.
+    try {
+      await updatePrice(1);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal(
+        "InsufficientSignatureWeight",
+      );
+    }
+
+    await setQuorumMode({ weight: {} }, 3);
+    await updatePrice(1);
+
+    const oracleState = await program.account.oracleState.fetch(oracleStatePda);
+    expect(
+      oracleState.oracleWeights
+        .slice(0, oracleState.totalOracles.toNumber())
+        .map((w) => w.toNumber()),
+    ).to.deep.equal([3, 1]);
+  });
+
+  it("Requires both quorums for a dual-attested feed", async () => {
+    const admin = Keypair.generate();
+    const secondaryAdmin = Keypair.generate();
+    const secondarySigner = ethers.Wallet.createRandom();
+
+    const { oracleStatePda } = await setupOracleState(
+      new anchor.BN(43),
+      new anchor.BN(2),
+      new anchor.BN(3600),
+      admin,
+    );
+
+    const secondaryOracleStateId = new anchor.BN(44);
+    const [secondaryOracleStatePda] =
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("oracle_state"),
+          secondaryOracleStateId.toBuffer("le", 8),
+        ],
+        program.programId,
+      );
+    await program.methods
+      .initializeOracle(
+        secondaryOracleStateId,
+        new anchor.BN(1),
+        new anchor.BN(3600),
+      )
+      .accounts({
+        oracleState: secondaryOracleStatePda,
+        payer: provider.wallet.publicKey,
+        admin: secondaryAdmin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([secondaryAdmin])
+      .rpc();
+    // The shared oracle only counts for the primary quorum.
+    for (const oracle of [
+      hexToUint8Array(secondarySigner.address),
+      expectedEthAddresses[0],
+    ]) {
+      await program.methods
+        .addOracle(Array.from(oracle))
+        .accounts({
+          oracleState: secondaryOracleStatePda,
+          admin: secondaryAdmin.publicKey,
+        })
+        .signers([secondaryAdmin])
+        .rpc();
+    }
+
+    const feedConfigPda = feedConfigAddress(oracleStatePda);
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "BTC", "USD", "Bitcoin / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .setSecondaryOracleState(Array.from(feedId))
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        secondaryOracleState: secondaryOracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const secondary = signPriceReport(secondarySigner, {
+      benchmark: benchmarkPrice,
+      ask: askPrice,
+      bid: bidPrice,
+    });
+
+    const updatePrice = (
+      reportSignatures: number[][],
+      reportRecoveryIds: Buffer,
+      secondaryOracleState: PublicKey | null,
+    ) =>
+      program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          reportSignatures,
+          reportRecoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigPda,
+          secondaryOracleState,
+          signerStats: null,
+        })
+        .rpc();
+
+    const expectError = async (update: Promise<string>, code: string) => {
+      try {
+        await update;
+        expect.fail("Expected an error but none was thrown");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(code);
+      }
+    };
+
+    const primarySignatures = signatures.map((sig) => Array.from(sig));
+    await expectError(
+      updatePrice(primarySignatures, recoveryIds, null),
+      "SecondaryOracleStateRequired",
+    );
+    await expectError(
+      updatePrice(
+        primarySignatures,
+        recoveryIds,
+        secondaryOracleStatePda,
+      ),
+      "SecondaryQuorumNotMet",
+    );
+    await expectError(
+      updatePrice(
+        [primarySignatures[0], ...secondary.signatures],
+        Buffer.concat([recoveryIds.subarray(0, 1), secondary.recoveryIds]),
+        secondaryOracleStatePda,
+      ),
+      "PrimaryQuorumNotMet",
+    );
+
+    await updatePrice(
+      [...primarySignatures, ...secondary.signatures],
+      Buffer.concat([recoveryIds, secondary.recoveryIds]),
+      secondaryOracleStatePda,
+    );
+    const priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+  });
+
+  it("Aggregates a feed across oracle states", async () => {
+    const admin = Keypair.generate();
+    const authority = provider.wallet.publicKey;
+    const oracleStates: PublicKey[] = [];
+    for (const id of [45, 46, 47]) {
+      const { oracleStatePda } = await setupOracleState(
+        new anchor.BN(id),
+        new anchor.BN(2),
+        new anchor.BN(3600),
+        admin,
+      );
+      oracleStates.push(oracleStatePda);
+    }
+    const priceFeeds = oracleStates.map(
+      (oracleStatePda) =>
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+          program.programId,
+        )[0],
+    );
+
+    // The third oracle state never publishes the feed.
+    for (const [index, oracleStatePda] of oracleStates.slice(0, 2).entries()) {
+      await program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeeds[index],
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    }
+
+    const [aggregateFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("aggregate_feed"), authority.toBuffer(), feedId],
+      program.programId,
+    );
+    // The fixture report is old, so allow ten years of staleness.
+    const setAggregateFeed = (minSources: number) =>
+      program.methods
+        .setAggregateFeed(
+          Array.from(feedId),
+          oracleStates,
+          new anchor.BN(10 * 365 * 24 * 60 * 60),
+          minSources,
+        )
+        .accounts({
+          aggregateFeed: aggregateFeedPda,
+          authority,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    const aggregate = () =>
+      program.methods
+        .aggregateFeed()
+        .accounts({ aggregateFeed: aggregateFeedPda })
+        .remainingAccounts(
+          oracleStates
+            .flatMap((oracleStatePda, index) => [
+              priceFeeds[index],
+              feedConfigAddress(oracleStatePda),
+            ])
+            .map((pubkey) => ({
+              pubkey,
+              isWritable: false,
+              isSigner: false,
+            })),
+        )
+        .rpc();
+
+    await setAggregateFeed(3);
+    try {
+      await aggregate();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal(
+        "InsufficientAggregateSources",
+      );
+    }
+
+    await setAggregateFeed(2);
+    await aggregate();
+
+    const aggregateFeed =
+      await program.account.aggregateFeed.fetch(aggregateFeedPda);
+    expect(aggregateFeed.sourceCount).to.equal(2);
+    expect(aggregateFeed.medianPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+    expect(aggregateFeed.minPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+    expect(aggregateFeed.oldestTimeStamp.toString()).to.equal(
+      validTimeStamp.toString(),
+    );
+
+    // A retired source is left out.
+    const feedConfigPda = feedConfigAddress(oracleStates[0]);
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "BTC", "USD", "Bitcoin / US Dollar")
+      .accounts({
+        oracleState: oracleStates[0],
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .setFeedStatus(Array.from(feedId), { retired: {} })
+      .accounts({
+        oracleState: oracleStates[0],
+        feedConfig: feedConfigPda,
+        feedRegistryPage: null,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+    try {
+      await aggregate();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal(
+        "InsufficientAggregateSources",
+      );
+    }
+  });
+
+  it("Refreshes derived feeds", async () => {
+    const admin = Keypair.generate();
+    const { oracleStatePda } = await setupOracleState(
+      new anchor.BN(48),
+      new anchor.BN(2),
+      new anchor.BN(3600),
+      admin,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .updatePrice(
+        feedId,
+        validTimeStamp,
+        observeTimeStamp,
+        nativeFee,
+        aproTokenFee,
+        expireAt,
+        benchmarkPrice,
+        askPrice,
+        bidPrice,
+        configDigest,
+        epochAndRound,
+        extraHash,
+        signatures,
+        recoveryIds,
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: null,
+        priceHistory: null,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        secondaryOracleState: null,
+        signerStats: null,
+      })
+      .rpc();
+
+    const feedConfigPda = feedConfigAddress(oracleStatePda);
+    const derivedFeed = async (
+      derivedId: number,
+      formula: object,
+      quoteFeedId: number[] | null,
+      decimals: number,
+      maxStaleness: number,
+    ) => {
+      const id = Buffer.alloc(32, derivedId);
+      const [derivedFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("derived_feed"), oracleStatePda.toBuffer(), id],
+        program.programId,
+      );
+      await program.methods
+        .setDerivedFeed(
+          Array.from(id),
+          formula as any,
+          Array.from(feedId),
+          quoteFeedId,
+          decimals,
+          new anchor.BN(maxStaleness),
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          derivedFeed: derivedFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      await program.methods
+        .refreshDerivedFeed()
+        .accounts({
+          derivedFeed: derivedFeedPda,
+          baseFeed: priceFeedPda,
+          baseFeedConfig: feedConfigPda,
+          quoteFeed: quoteFeedId ? priceFeedPda : null,
+          quoteFeedConfig: quoteFeedId ? feedConfigPda : null,
+        })
+        .rpc();
+      return program.account.derivedFeed.fetch(derivedFeedPda);
+    };
+
+    // The fixture report is old, so allow ten years of staleness.
+    const maxStaleness = 10 * 365 * 24 * 60 * 60;
+
+    // The input's decimals come from its feed config.
+    try {
+      await derivedFeed(1, { invert: {} }, null, 8, maxStaleness);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("MissingSourceDecimals");
+    }
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "BTC", "USD", "Bitcoin / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const inverted = await derivedFeed(1, { invert: {} }, null, 8, maxStaleness);
+    expect(inverted.price.toString()).to.equal(
+      new anchor.BN(10).pow(new anchor.BN(26)).div(benchmarkPrice).toString(),
+    );
+    expect(inverted.oldestTimeStamp.toString()).to.equal(
+      validTimeStamp.toString(),
+    );
+
+    const squared = await derivedFeed(
+      2,
+      { product: {} },
+      Array.from(feedId),
+      18,
+      maxStaleness,
+    );
+    expect(squared.price.toString()).to.equal(
+      benchmarkPrice
+        .mul(benchmarkPrice)
+        .div(new anchor.BN(10).pow(new anchor.BN(18)))
+        .toString(),
+    );
+
+    try {
+      await derivedFeed(3, { invert: {} }, null, 8, 60);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("StaleDerivedInput");
+    }
+
+    await program.methods
+      .setFallbackPrice(
+        Array.from(feedId),
+        new anchor.BN(123),
+        new anchor.BN(600),
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        authority: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+    try {
+      await derivedFeed(4, { invert: {} }, null, 8, maxStaleness);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnusableSourceFeed");
+    }
+  });
+
+  it("Refreshes basket feeds", async () => {
+    const admin = Keypair.generate();
+    const { oracleStatePda } = await setupOracleState(
+      new anchor.BN(49),
+      new anchor.BN(2),
+      new anchor.BN(3600),
+      admin,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .updatePrice(
+        feedId,
+        validTimeStamp,
+        observeTimeStamp,
+        nativeFee,
+        aproTokenFee,
+        expireAt,
+        benchmarkPrice,
+        askPrice,
+        bidPrice,
+        configDigest,
+        epochAndRound,
+        extraHash,
+        signatures,
+        recoveryIds,
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: null,
+        priceHistory: null,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        secondaryOracleState: null,
+        signerStats: null,
+      })
+      .rpc();
+
+    const feedConfigPda = feedConfigAddress(oracleStatePda);
+    const basketId = Buffer.alloc(32, 1);
+    const [basketFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("basket_feed"), oracleStatePda.toBuffer(), basketId],
+      program.programId,
+    );
+    // Weights have 9 decimals: 0.5 and 1.5 of the same feed.
+    const setBasketFeed = (maxStaleness: number) =>
+      program.methods
+        .setBasketFeed(
+          Array.from(basketId),
+          [
+            {
+              feedId: Array.from(feedId),
+              weight: new anchor.BN(500_000_000),
+            },
+            {
+              feedId: Array.from(feedId),
+              weight: new anchor.BN(1_500_000_000),
+            },
+          ],
+          18,
+          new anchor.BN(maxStaleness),
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          basketFeed: basketFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    const refresh = () =>
+      program.methods
+        .refreshBasketFeed()
+        .accounts({ basketFeed: basketFeedPda })
+        .remainingAccounts(
+          [priceFeedPda, feedConfigPda, priceFeedPda, feedConfigPda].map(
+            (pubkey) => ({
+              pubkey,
+              isWritable: false,
+              isSigner: false,
+            }),
+          ),
+        )
+        .rpc();
+
+    // The constituents' decimals come from their feed config.
+    await setBasketFeed(60);
+    try {
+      await refresh();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("MissingSourceDecimals");
+    }
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "BTC", "USD", "Bitcoin / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    try {
+      await refresh();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("StaleBasketConstituent");
+    }
+
+    // The fixture report is old, so allow ten years of staleness.
+    await setBasketFeed(10 * 365 * 24 * 60 * 60);
+    await refresh();
+
+    const basketFeed = await program.account.basketFeed.fetch(basketFeedPda);
+    expect(basketFeed.value.toString()).to.equal(
+      benchmarkPrice.mul(new anchor.BN(2)).toString(),
+    );
+    expect(
+      basketFeed.constituents.map((c) => c.validTimeStamp.toString()),
+    ).to.deep.equal([validTimeStamp.toString(), validTimeStamp.toString()]);
+
+    await program.methods
+      .setFeedStatus(Array.from(feedId), { retired: {} })
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        feedRegistryPage: null,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+    try {
+      await refresh();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnusableSourceFeed");
+    }
+  });
+
+  it("Rejects migrating an account already in the zero-copy layout", async () => {
+    const admin = Keypair.generate();
+    const { oracleStatePda } = await setupOracleState(
+      new anchor.BN(50),
+      new anchor.BN(2),
+      new anchor.BN(3600),
+      admin,
+    );
+
+    const account = await provider.connection.getAccountInfo(oracleStatePda);
+    expect(account.data.length).to.equal(720);
+
+    try {
+      await program.methods
+        .migrateOracleState()
+        .accounts({
+          oracleState: oracleStatePda,
+          payer: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidLegacyAccount");
+    }
+  });
+
+  it("Migrates accounts in the baseline layouts", async () => {
+    // Both accounts are loaded from tests/fixtures in the Borsh layouts
+    // of the baseline program. The feed holds a report one second older
+    // than the fixture report.
+    const legacyAdmin = Keypair.fromSeed(new Uint8Array(32).fill(58));
+    const [oracleStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_state"), new anchor.BN(58).toBuffer("le", 8)],
+      program.programId,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+    let account = await provider.connection.getAccountInfo(priceFeedPda);
+    expect(account.data.length).to.equal(248);
+
+    // Neither the oracle state nor its feeds can be used before the oracle
+    // state is migrated.
+    try {
+      await program.methods
+        .updateAdmin(legacyAdmin.publicKey)
+        .accounts({
+          oracleState: oracleStatePda,
+          admin: legacyAdmin.publicKey,
+        })
+        .signers([legacyAdmin])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AccountNotMigrated");
+    }
+    try {
+      await program.methods
+        .migratePriceFeed(Array.from(feedId))
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AccountNotMigrated");
+    }
+
+    await program.methods
+      .migrateOracleState()
+      .accounts({
+        oracleState: oracleStatePda,
+        payer: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const oracleState = await program.account.oracleState.fetch(oracleStatePda);
+    expect(oracleState.id.toNumber()).to.equal(58);
+    expect(oracleState.requiredSignatures.toNumber()).to.equal(2);
+    expect(oracleState.admin.toBase58()).to.equal(
+      legacyAdmin.publicKey.toBase58(),
+    );
+    expect(oracleState.expirationPeriod.toNumber()).to.equal(3600);
+    expect(
+      oracleState.oracles
+        .slice(0, oracleState.totalOracles.toNumber())
+        .map((oracle) => Buffer.from(oracle).toString("hex")),
+    ).to.deep.equal(
+      expectedEthAddresses.map((oracle) => Buffer.from(oracle).toString("hex")),
+    );
+    expect(
+      oracleState.oracleWeights.slice(0, 2).map((w) => w.toNumber()),
+    ).to.deep.equal([1, 1]);
+
+    // The feed cannot be used before it is migrated.
+    try {
+      await program.methods
+        .setFallbackPrice(
+          Array.from(feedId),
+          new anchor.BN(123),
+          new anchor.BN(600),
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          authority: legacyAdmin.publicKey,
+        })
+        .signers([legacyAdmin])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AccountNotMigrated");
+    }
+
+    await program.methods
+      .migratePriceFeed(Array.from(feedId))
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        payer: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    account = await provider.connection.getAccountInfo(priceFeedPda);
+    expect(account.data.length).to.equal(392);
+    let priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(Buffer.from(priceFeed.feedId).equals(Buffer.from(feedId))).to.be
+      .true;
+    expect(priceFeed.validTimeStamp.toString()).to.equal(
+      validTimeStamp.subn(1).toString(),
+    );
+    expect(priceFeed.observeTimeStamp.toString()).to.equal(
+      observeTimeStamp.subn(1).toString(),
+    );
+    expect(priceFeed.expireAt.toString()).to.equal(expireAt.toString());
+    expect(priceFeed.aproTokenFee.toNumber()).to.equal(7);
+    expect(priceFeed.benchmarkPrice.toNumber()).to.equal(1000);
+    expect(priceFeed.askPrice.toNumber()).to.equal(1001);
+    expect(priceFeed.bidPrice.toNumber()).to.equal(999);
+    expect(priceFeed.epochAndRound.toNumber()).to.equal(5);
+    expect(priceFeed.breakerState).to.equal(0);
+
+    await program.methods
+      .updatePrice(
+        feedId,
+        validTimeStamp,
+        observeTimeStamp,
+        nativeFee,
+        aproTokenFee,
+        expireAt,
+        benchmarkPrice,
+        askPrice,
+        bidPrice,
+        configDigest,
+        epochAndRound,
+        extraHash,
+        signatures,
+        recoveryIds,
+      )
+      .accounts({
+        oracleState: oracleStatePda,
+        priceFeed: priceFeedPda,
+        payer: provider.wallet.publicKey,
+        admin: legacyAdmin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: null,
+        priceHistory: null,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        secondaryOracleState: null,
+        signerStats: null,
+      })
+      .rpc();
+
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+  });
+
+  it("Rejects report timestamps that do not fit the feed", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(59),
+      signer,
+      admin,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    const updatePrice = (reportTime: anchor.BN) => {
+      const { signatures, recoveryIds } = signPriceReport(
+        signer,
+        { benchmark: benchmarkPrice, ask: askPrice, bid: bidPrice },
+        reportTime,
+      );
+      return program.methods
+        .updatePrice(
+          feedId,
+          reportTime,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    };
+
+    // Feeds store timestamps as u64.
+    const u64Max = new anchor.BN(2).pow(new anchor.BN(64)).subn(1);
+    try {
+      await updatePrice(u64Max.addn(1));
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("ValueOutOfRange");
+    }
+
+    await updatePrice(validTimeStamp);
+    const priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.validTimeStamp.toString()).to.equal(
+      validTimeStamp.toString(),
+    );
+  });
+
+  it("Records the slot, time, updater and signers of each update", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(60),
+      signer,
+      admin,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    const updatePrice = (reportTime: anchor.BN) => {
+      const { signatures, recoveryIds } = signPriceReport(
+        signer,
+        { benchmark: benchmarkPrice, ask: askPrice, bid: bidPrice },
+        reportTime,
+      );
+      return program.methods
+        .updatePrice(
+          feedId,
+          reportTime,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    };
+
+    await updatePrice(validTimeStamp.subn(1));
+    let priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    const firstSlot = priceFeed.lastUpdateSlot.toNumber();
+    expect(firstSlot).to.be.greaterThan(0);
+    expect(priceFeed.lastUpdateUnixTs.toNumber()).to.be.greaterThan(0);
+    expect(priceFeed.updateCount.toNumber()).to.equal(1);
+
+    await updatePrice(validTimeStamp);
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    // The only oracle sits at index 0 of the oracle state.
+    expect(priceFeed.signerBitmap).to.equal(0b1);
+    expect(priceFeed.updateCount.toNumber()).to.equal(2);
+    expect(priceFeed.updater.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58(),
+    );
+    expect(priceFeed.lastUpdateSlot.toNumber()).to.be.at.least(firstSlot);
+    expect(priceFeed.lastUpdateUnixTs.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("Tracks which oracles sign reports", async () => {
+    const admin = Keypair.generate();
+    const { oracleStatePda } = await setupOracleState(
+      new anchor.BN(51),
+      new anchor.BN(2),
+      new anchor.BN(3600),
+      admin,
+    );
+
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+    const [signerStatsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("signer_stats"), oracleStatePda.toBuffer()],
+      program.programId,
+    );
+
+    await program.methods
+      .initializeSignerStats()
+      .accounts({
+        oracleState: oracleStatePda,
+        signerStats: signerStatsPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const updatePrice = (signerStats: anchor.web3.PublicKey | null) =>
+      program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats,
+        })
+        .rpc();
+
+    // Once initialized, the signer stats must record every report.
+    try {
+      await updatePrice(null);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("SignerStatsRequired");
+    }
+
+    await updatePrice(signerStatsPda);
+
+    const signerStats = await program.account.signerStats.fetch(
+      signerStatsPda,
+    );
+    expect(signerStats.reportCount.toNumber()).to.equal(1);
+    expect(signerStats.signers.length).to.equal(expectedEthAddresses.length);
+    signerStats.signers.forEach((activity, index) => {
+      expect(Buffer.from(activity.oracle).toString("hex")).to.equal(
+        Buffer.from(expectedEthAddresses[index]).toString("hex"),
+      );
+      expect(activity.participation.toNumber()).to.equal(1);
+      expect(activity.lastSignedSlot.toNumber()).to.be.greaterThan(0);
+    });
+  });
+
+  it("Records the push policy trigger and rejects unscheduled updates", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(52),
+      signer,
+      admin,
+    );
+    const feedConfigPda = feedConfigAddress(oracleStatePda);
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "ETH", "USD", "Ether / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .setPushPolicy(Array.from(feedId), {
+        heartbeatSeconds: new anchor.BN(60),
+        deviationBps: new anchor.BN(50),
+        rejectUnscheduled: true,
+      })
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const updatePrice = (reportTime: anchor.BN) => {
+      const { signatures, recoveryIds } = signPriceReport(
+        signer,
+        { benchmark: benchmarkPrice, ask: askPrice, bid: bidPrice },
+        reportTime,
+      );
+      return program.methods
+        .updatePrice(
+          feedId,
+          reportTime,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigPda,
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    };
+
+    // The first report of a feed counts as a heartbeat.
+    await updatePrice(validTimeStamp);
+    const priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.updateReason).to.equal(1);
+
+    // A second later at the same price is neither a heartbeat nor a
+    // deviation.
+    try {
+      await updatePrice(validTimeStamp.addn(1));
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UpdateNotDue");
+    }
+  });
+
+  it("Rate limits feed updates unless overridden", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(53),
+      signer,
+      admin,
+    );
+    const feedConfigPda = feedConfigAddress(oracleStatePda);
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "ETH", "USD", "Ether / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .setRateLimit(Array.from(feedId), new anchor.BN(0), new anchor.BN(3600))
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const updatePrice = (reportTime: anchor.BN) => {
+      const { signatures, recoveryIds } = signPriceReport(
+        signer,
+        { benchmark: benchmarkPrice, ask: askPrice, bid: bidPrice },
+        reportTime,
+      );
+      return program.methods
+        .updatePrice(
+          feedId,
+          reportTime,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigPda,
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    };
+
+    await updatePrice(validTimeStamp);
+    try {
+      await updatePrice(validTimeStamp.addn(1));
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UpdateTooFrequent");
+    }
+
+    await program.methods
+      .overrideRateLimit(Array.from(feedId), new anchor.BN(4102444800))
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    await updatePrice(validTimeStamp.addn(2));
+    const priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.updateCount.toNumber()).to.equal(2);
+  });
+
+  it("Serves a manual fallback price until a newer signed report", async () => {
+    const admin = Keypair.generate();
+    const signer = ethers.Wallet.createRandom();
+    const { oracleStatePda } = await setupSignerOracleState(
+      new anchor.BN(54),
+      signer,
+      admin,
+    );
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    const updatePrice = (reportTime: anchor.BN) => {
+      const { signatures, recoveryIds } = signPriceReport(
+        signer,
+        { benchmark: benchmarkPrice, ask: askPrice, bid: bidPrice },
+        reportTime,
+      );
+      return program.methods
+        .updatePrice(
+          feedId,
+          reportTime,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+    };
+    const setFallbackPrice = (duration: number, authority = admin) =>
+      program.methods
+        .setFallbackPrice(
+          Array.from(feedId),
+          new anchor.BN(123),
+          new anchor.BN(duration),
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          feedConfig: feedConfigAddress(oracleStatePda),
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    await updatePrice(validTimeStamp);
+
+    try {
+      await setFallbackPrice(2 * 3600);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidFallbackDuration");
+    }
+
+    await setFallbackPrice(600);
+    let priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.priceSource).to.equal(1);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal("123");
+    expect(priceFeed.expireAt.sub(priceFeed.validTimeStamp).toNumber()).to.equal(
+      600,
+    );
+
+    // Reports from before the fallback was set cannot clear it.
+    try {
+      await updatePrice(validTimeStamp);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("StaleReport");
+    }
+
+    // A report newer than the fallback replaces it.
+    await updatePrice(priceFeed.validTimeStamp.addn(1));
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.priceSource).to.equal(0);
+    expect(priceFeed.benchmarkPrice.toString()).to.equal(
+      benchmarkPrice.toString(),
+    );
+
+    // The pauser may set a fallback too, other keys may not.
+    const pauser = Keypair.generate();
+    try {
+      await setFallbackPrice(600, pauser);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnauthorizedPauser");
+    }
+    await program.methods
+      .setPauser(pauser.publicKey)
+      .accounts({
+        oracleState: oracleStatePda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+    await setFallbackPrice(600, pauser);
+    priceFeed = await program.account.priceFeed.fetch(priceFeedPda);
+    expect(priceFeed.priceSource).to.equal(1);
+
+    // A retired feed cannot be revived through a fallback.
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "ETH", "USD", "Ether / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .setFeedStatus(Array.from(feedId), { retired: {} })
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigAddress(oracleStatePda),
+        feedRegistryPage: null,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+    try {
+      await setFallbackPrice(600);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("FeedRetired");
+    }
+  });
+
+  it("Retires a feed at its sunset and refuses further updates", async () => {
+    const admin = Keypair.generate();
+    const { oracleStatePda } = await setupOracleState(
+      new anchor.BN(55),
+      new anchor.BN(2),
+      new anchor.BN(3600),
+      admin,
+    );
+    const feedConfigPda = feedConfigAddress(oracleStatePda);
+    const [priceFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("price_feed"), oracleStatePda.toBuffer(), feedId],
+      program.programId,
+    );
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "ETH", "USD", "Ether / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const setFeedSunset = (sunsetAt: number) =>
+      program.methods
+        .setFeedSunset(Array.from(feedId), new anchor.BN(sunsetAt))
+        .accounts({
+          oracleState: oracleStatePda,
+          feedConfig: feedConfigPda,
+          feedRegistryPage: null,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    const setFeedStatus = (status: object) =>
+      program.methods
+        .setFeedStatus(Array.from(feedId), status as any)
+        .accounts({
+          oracleState: oracleStatePda,
+          feedConfig: feedConfigPda,
+          feedRegistryPage: null,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+    const now = Math.floor(Date.now() / 1000);
+    try {
+      await setFeedSunset(now - 60);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidSunset");
+    }
+
+    await setFeedSunset(now + 86400);
+    let feedConfig = await program.account.feedConfig.fetch(feedConfigPda);
+    expect(feedConfig.status).to.deep.equal({ deprecated: {} });
+    expect(feedConfig.sunsetAt.toNumber()).to.equal(now + 86400);
+
+    await setFeedStatus({ retired: {} });
+    try {
+      await program.methods
+        .updatePrice(
+          feedId,
+          validTimeStamp,
+          observeTimeStamp,
+          nativeFee,
+          aproTokenFee,
+          expireAt,
+          benchmarkPrice,
+          askPrice,
+          bidPrice,
+          configDigest,
+          epochAndRound,
+          extraHash,
+          signatures,
+          recoveryIds,
+        )
+        .accounts({
+          oracleState: oracleStatePda,
+          priceFeed: priceFeedPda,
+          payer: provider.wallet.publicKey,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          operatorRegistry: null,
+          priceHistory: null,
+          priceAccumulator: null,
+          feedRegistry: null,
+          feedRegistryPage: null,
+          feedConfig: feedConfigPda,
+          secondaryOracleState: null,
+          signerStats: null,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("FeedRetired");
+    }
+
+    try {
+      await setFeedStatus({ active: {} });
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("FeedRetired");
+    }
+    feedConfig = await program.account.feedConfig.fetch(feedConfigPda);
+    expect(feedConfig.status).to.deep.equal({ retired: {} });
+  });
+
+  it("Keeps a feed retired once its sunset has passed", async () => {
+    const admin = Keypair.generate();
+    const { oracleStatePda } = await setupOracleState(
+      new anchor.BN(61),
+      new anchor.BN(2),
+      new anchor.BN(3600),
+      admin,
+    );
+    const feedConfigPda = feedConfigAddress(oracleStatePda);
+
+    await program.methods
+      .setFeedConfig(Array.from(feedId), 18, "ETH", "USD", "Ether / US Dollar")
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        payer: provider.wallet.publicKey,
+        admin: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const clusterTime = async () =>
+      provider.connection.getBlockTime(await provider.connection.getSlot());
+    const sunsetAt = (await clusterTime()) + 2;
+    await program.methods
+      .setFeedSunset(Array.from(feedId), new anchor.BN(sunsetAt))
+      .accounts({
+        oracleState: oracleStatePda,
+        feedConfig: feedConfigPda,
+        feedRegistryPage: null,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+    while ((await clusterTime()) <= sunsetAt) {
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+
+    // The stored status is still deprecated, but the feed is retired.
+    try {
+      await program.methods
+        .setFeedStatus(Array.from(feedId), { active: {} })
+        .accounts({
+          oracleState: oracleStatePda,
+          feedConfig: feedConfigPda,
+          feedRegistryPage: null,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("FeedRetired");
+    }
+    const feedConfig = await program.account.feedConfig.fetch(feedConfigPda);
+    expect(feedConfig.status).to.deep.equal({ deprecated: {} });
+    expect(feedConfig.sunsetAt.toNumber()).to.equal(sunsetAt);
+  });
+
   it("Updates admin successfully", async () => {
     const admin = Keypair.generate();
     const newAdmin = Keypair.generate();
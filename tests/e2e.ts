@@ -215,6 +215,10 @@ describe("E2E Tests", () => {
       [Buffer.from("price_feed"), oracleStatePda.toBuffer(), data.feedID],
       oracle_program.programId,
     );
+    const [feedConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("feed_config"), oracleStatePda.toBuffer(), data.feedID],
+      oracle_program.programId,
+    );
 
     // First, update the price feed using the oracle program
     await oracle_program.methods
@@ -240,6 +244,14 @@ describe("E2E Tests", () => {
         payer: provider.wallet.publicKey,
         admin: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        operatorRegistry: null,
+        priceHistory: null,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigPda,
+        secondaryOracleState: null,
+        signerStats: null,
       })
       .rpc();
 
@@ -276,6 +288,10 @@ describe("E2E Tests", () => {
       oracle_program.programId,
     );
 
+    const [feedConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("feed_config"), oracleStatePda.toBuffer(), data.feedID],
+      oracle_program.programId,
+    );
     const updateData = {
       feedId: Array.from(data.feedID),
       validTimeStamp: data.validFromTimestamp,
@@ -317,6 +333,14 @@ describe("E2E Tests", () => {
         admin: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
         oracleProgram: oracle_program.programId,
+        operatorRegistry: null,
+        priceHistory: null,
+        priceAccumulator: null,
+        feedRegistry: null,
+        feedRegistryPage: null,
+        feedConfig: feedConfigPda,
+        secondaryOracleState: null,
+        signerStats: null,
       })
       .rpc()
       .catch(async (error) => {